@@ -31,7 +31,7 @@ fn main() {
     assert_eq!(res.get("num").unwrap()[0], MyMessage::Gt10);
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 enum MyMessage {
     NameRequierd,
     NameStartWith,
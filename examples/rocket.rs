@@ -26,7 +26,7 @@ fn index(name: String, second: String) -> String {
             .map(String::from),
     ) {
         Ok((name, _)) => format!("Hello, {name}!"),
-        Err(_) => format!("name is required"),
+        Err(_) => "name is required".to_string(),
     }
 }
 
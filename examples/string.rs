@@ -1,3 +1,5 @@
+#![allow(dead_code, clippy::ptr_arg)]
+
 use valitron::{
     available::{Email, Message, Required, Trim},
     register::string::Validator,
@@ -67,6 +69,6 @@ fn validate_password(pass: &mut String) -> Result<(), Message> {
     }
 }
 
-fn validate_gender(gender: &mut String) -> Result<(), Message> {
+fn validate_gender(_gender: &mut String) -> Result<(), Message> {
     Ok(())
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use diesel::{Connection, PgConnection, Queryable, Selectable};
 use valitron::{
     available::{Email, Trim},
@@ -95,10 +97,10 @@ impl StringRule for UniqueEmail {
             .load(conn)
             .unwrap();
 
-        results.len() == 0
+        results.is_empty()
     }
 
     fn message(&self) -> Self::Message {
-        format!("email is existing")
+        "email is existing".to_string()
     }
 }
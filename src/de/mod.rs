@@ -20,7 +20,7 @@ impl Value {
     }
 
     #[cold]
-    fn unexpected(&self) -> Unexpected {
+    fn unexpected(&self) -> Unexpected<'_> {
         match self {
             Value::Uint8(n) => Unexpected::Unsigned(*n as u64),
             Value::Uint16(n) => Unexpected::Unsigned(*n as u64),
@@ -56,6 +56,7 @@
 //! ## Available Rules
 //!
 //! - [`Compare`]
+//! - [`Conditional`]: `RequiredIf`, `RequiredUnless`, `RequiredWith`, `RequiredWithout`, `Prohibited`, `ExcludeIf` and `When`
 //! - [`Confirm`]
 //! - [`Contains`]
 //! - [`Email`]
@@ -88,12 +89,33 @@
 //!
 //! anything types implemented [`Rule`] trait can be used as a rule
 //!
+//! ## Async Rule
+//!
+//! I/O-backed checks can implement [`AsyncRule`] trait, and run it by [`validate_async`],
+//! it requires `async` feature.
+//!
+//! ## JSON Schema
+//!
+//! the constraints of [`Validator`] can be exported as JSON Schema by [`json_schema`],
+//! it requires `schema` feature.
+//!
+//! ## Derive
+//!
+//! the validator of struct can be declared by `#[derive(Validate)]` and field attributes,
+//! e.g. `#[valitron(required, length(6..=12))]`, it requires `derive` feature.
+//!
+//! besides, [`validator!`] and [`messages!`] are the concise way to declare validator.
+//!
 //! [`map`]: crate::register::Validator::map
 //! [`Rule`]: crate::Rule
+//! [`AsyncRule`]: crate::rule::asynchronous
+//! [`validate_async`]: crate::register::Validator::validate_async
+//! [`json_schema`]: crate::register::schema
 //! [`Message`]: crate::available::Message
 //! [`Required`]: crate::available::required
 //! [`Email`]: crate::available::email
 //! [`Compare`]: crate::available::compare
+//! [`Conditional`]: crate::available::condition
 //! [`Contains`]: crate::available::contains
 //! [`StartWith`]: crate::available::start_with
 //! [`EndWith`]: crate::available::end_with
@@ -119,8 +141,13 @@ pub mod value;
 #[macro_use]
 pub(crate) mod macros;
 
-pub use register::{ValidPhrase, Validatable, Validator};
-pub use rule::{custom, Rule, RuleExt};
+pub use register::{BuildError, ValidPhrase, Validatable, Validate, Validator};
+pub use rule::combinator::{all_of, any_of, not, xor};
+#[cfg(feature = "async")]
+pub use rule::{async_rule, AsyncRule, AsyncRuleExt};
+pub use rule::{checked, custom, custom_with_context, named, ClosureMessage, Rule, RuleExt};
+#[cfg(feature = "derive")]
+pub use valitron_derive::Validate;
 pub use value::{FromValue, Value, ValueMap};
 
 #[cfg(feature = "full")]
@@ -59,3 +59,169 @@ macro_rules! panic_on_err {
         }
     };
 }
+
+/// # Declare validator
+///
+/// fields are idents, e.g. `name` or `address.city`, or strings, e.g. `"tags[*]"`,
+/// the messages after `;` are declared by [`messages!`](crate::messages):
+///
+/// ```rust
+/// # #[cfg(feature = "full")]
+/// # {
+/// # use serde::Serialize;
+/// # use valitron::{available::{Length, Message, Required, StartWith}, validator, RuleExt};
+/// let validator = validator! {
+///     name: Required.and(Length(1..=20)),
+///     address.city: Required,
+///     "tags[*]": StartWith("#");
+///     messages {
+///         name.required => "name is required",
+///         "tags[*].start_with" => "tag should start with #",
+///     }
+/// };
+///
+/// #[derive(Serialize)]
+/// struct Address {
+///     city: &'static str,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Input {
+///     name: &'static str,
+///     address: Address,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let err = validator
+///     .validate(Input {
+///         name: "",
+///         address: Address { city: "" },
+///         tags: vec!["#rust", "go"],
+///     })
+///     .unwrap_err();
+///
+/// assert_eq!(err.get("name").unwrap()[0], Message::from("name is required"));
+/// assert_eq!(err.get("tags[1]").unwrap()[0], Message::from("tag should start with #"));
+/// assert_eq!(err.len(), 3);
+/// # }
+/// ```
+///
+/// it expands to the calls of [`Validator::rule`] and [`Validator::message`],
+/// so it panics on the same errors, e.g. the rule of message key is not found.
+///
+/// for the same reason, `messages` needs one field at least before it:
+///
+/// ```compile_fail
+/// # use valitron::validator;
+/// let validator = validator! {
+///     messages {
+///         name.required => "name is required",
+///     }
+/// };
+/// ```
+///
+/// [`Validator::rule`]: crate::register::InnerValidator::rule
+/// [`Validator::message`]: crate::register::InnerValidator::message
+#[macro_export]
+macro_rules! validator {
+    (@rules [$($built:tt)*] $(;)?) => {
+        $($built)*
+    };
+    (@rules [$($built:tt)*] ; messages { $($messages:tt)* } $(;)?) => {
+        $($built)*.message($crate::messages! { $($messages)* })
+    };
+    (@rules [$($built:tt)*] $first:ident $(. $field:ident)* : $rule:expr $(, $($rest:tt)*)?) => {
+        $crate::validator!(
+            @rules [$($built)*.rule(concat!(stringify!($first) $(, ".", stringify!($field))*), $rule)]
+            $($($rest)*)?
+        )
+    };
+    (@rules [$($built:tt)*] $first:ident $(. $field:ident)* : $rule:expr ; $($rest:tt)*) => {
+        $crate::validator!(
+            @rules [$($built)*.rule(concat!(stringify!($first) $(, ".", stringify!($field))*), $rule)]
+            ; $($rest)*
+        )
+    };
+    (@rules [$($built:tt)*] $field:literal : $rule:expr $(, $($rest:tt)*)?) => {
+        $crate::validator!(@rules [$($built)*.rule($field, $rule)] $($($rest)*)?)
+    };
+    (@rules [$($built:tt)*] $field:literal : $rule:expr ; $($rest:tt)*) => {
+        $crate::validator!(@rules [$($built)*.rule($field, $rule)] ; $($rest)*)
+    };
+    (messages { $($messages:tt)* } $(;)?) => {
+        compile_error!("`messages` needs one field at least, e.g. `name: Required; messages { .. }`")
+    };
+    ($($tt:tt)*) => {
+        $crate::validator!(@rules [$crate::Validator::new()] $($tt)*)
+    };
+}
+
+/// # Declare messages
+///
+/// keys are `field.rule` idents, e.g. `name.required`, or strings, e.g. `"tags[*].start_with"`,
+/// it expands to an array, which is used by [`Validator::message`]:
+///
+/// ```rust
+/// # #[cfg(feature = "full")]
+/// # {
+/// # use valitron::{available::Required, messages, Validator};
+/// let validator = Validator::new()
+///     .rule("name", Required)
+///     .rule("tags[0]", Required)
+///     .message(messages! {
+///         name.required => "name is required",
+///         "tags[0].required" => "the first tag is required",
+///     });
+/// # }
+/// ```
+///
+/// the format of keys is checked, it panics on the malformed key:
+///
+/// ```should_panic
+/// # use valitron::messages;
+/// let messages = messages! {
+///     "tags[0.required" => "the first tag is required",
+/// };
+/// ```
+///
+/// [`Validator::message`]: crate::register::InnerValidator::message
+#[macro_export]
+macro_rules! messages {
+    (@keys [$($built:tt)*]) => {
+        [$($built)*]
+    };
+    (@keys [$($built:tt)*] $first:ident $(. $key:ident)+ => $message:expr $(, $($rest:tt)*)?) => {
+        $crate::messages!(
+            @keys [
+                $($built)*
+                (
+                    $crate::register::__message_key(
+                        concat!(stringify!($first) $(, ".", stringify!($key))+)
+                    ),
+                    $message
+                ),
+            ]
+            $($($rest)*)?
+        )
+    };
+    (@keys [$($built:tt)*] $key:literal => $message:expr $(, $($rest:tt)*)?) => {
+        $crate::messages!(
+            @keys [$($built)* ($crate::register::__message_key($key), $message),]
+            $($($rest)*)?
+        )
+    };
+    ($($tt:tt)*) => {
+        $crate::messages!(@keys [] $($tt)*)
+    };
+}
+
+/// Private API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_missing_message {
+    () => {
+        fn missing_message(&self) -> Self::Message {
+            $crate::available::Message::new($crate::available::MessageKind::Missing)
+        }
+    };
+}
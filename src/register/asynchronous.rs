@@ -0,0 +1,108 @@
+//! run validator with async rules
+
+use std::any::Any;
+
+use serde::Serialize;
+
+use crate::{ser::Serializer, value::ValueMap};
+
+use super::{get_message, replace_label, Validator, ValidatorError, Walker};
+
+impl<M: 'static> Validator<'_, M> {
+    /// run validate without modifiable, async rules and sync rules are called in declared order
+    pub async fn validate_async<T>(&self, data: T) -> Result<(), ValidatorError<M>>
+    where
+        T: Serialize,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.inner_validate_async(&mut value_map).await.ok()
+    }
+
+    /// run validate with modifiable, async rules and sync rules are called in declared order
+    pub async fn validate_mut_async<'de, T>(&self, data: T) -> Result<T, ValidatorError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.inner_validate_async(&mut value_map)
+            .await
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
+
+    /// run validate with external context, async rules and sync rules are called in declared order,
+    /// see [`Validator::validate_with_context`]
+    pub async fn validate_async_with_context<T, C>(
+        &self,
+        data: T,
+        context: C,
+    ) -> Result<(), ValidatorError<M>>
+    where
+        T: Serialize,
+        C: Any + Send + Sync,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::with_context(value, context);
+
+        self.inner_validate_async(&mut value_map).await.ok()
+    }
+
+    /// run validate with modifiable and external context, async rules and sync rules are called in declared order
+    pub async fn validate_mut_async_with_context<'de, T, C>(
+        &self,
+        data: T,
+        context: C,
+    ) -> Result<T, ValidatorError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        C: Any + Send + Sync,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::with_context(value, context);
+
+        self.inner_validate_async(&mut value_map)
+            .await
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
+
+    async fn inner_validate_async(&self, value_map: &mut ValueMap) -> ValidatorError<M> {
+        let mut resp_message = ValidatorError::with_capacity(self.rules.len());
+
+        let mut walker = Walker::new(self);
+        while let Some(step) = walker.next(&value_map.value) {
+            value_map.index(step.field);
+
+            let field_msg = step
+                .rules
+                .call_async(value_map)
+                .await
+                .into_iter()
+                .map(|(rule, instance, msg)| {
+                    let msg =
+                        get_message(&self.message, &step.names, rule, instance).unwrap_or(msg);
+                    replace_label(msg, step.label.unwrap_or(value_map.as_index().as_str()))
+                })
+                .collect();
+
+            resp_message.push(value_map.take_index(), field_msg);
+
+            if self.is_bail && !resp_message.is_empty() {
+                resp_message.shrink_to(1);
+                return resp_message;
+            }
+        }
+
+        resp_message.shrink_to_fit();
+
+        resp_message
+    }
+}
@@ -0,0 +1,540 @@
+//! # Build validator from config
+//!
+//! the config document is deserialized by any serde format, e.g. JSON, TOML or YAML,
+//! rules are built by [`Registry`], so the rules registered by users are available.
+//!
+//! ```rust
+//! # use serde::Serialize;
+//! # use valitron::{
+//! #     available::Message,
+//! #     register::{config::ValidatorConfig, registry::Registry, Validator},
+//! # };
+//! let config: ValidatorConfig = serde_json::from_str(
+//!     r#"{
+//!         "fields": [
+//!             {
+//!                 "field": "name",
+//!                 "rules": ["required", { "length": { "min": 3, "max": 12 } }],
+//!                 "messages": { "length": "name should be 3 to 12 characters" },
+//!                 "bail": true
+//!             },
+//!             { "field": "password", "rules": [{ "confirm": "$password2" }] }
+//!         ]
+//!     }"#,
+//! )
+//! .unwrap();
+//!
+//! let validator = Validator::from_config(&config, &Registry::<Message>::new()).unwrap();
+//!
+//! #[derive(Serialize)]
+//! struct Input {
+//!     name: &'static str,
+//!     password: &'static str,
+//!     password2: &'static str,
+//! }
+//!
+//! let err = validator
+//!     .validate(Input { name: "ab", password: "foo", password2: "bar" })
+//!     .unwrap_err();
+//! assert_eq!(err.get("name").unwrap()[0].to_string(), "name should be 3 to 12 characters");
+//! assert_eq!(err.get("password").unwrap().len(), 1);
+//! ```
+//!
+//! the same document in TOML:
+//!
+//! ```toml
+//! [[fields]]
+//! field = "name"
+//! rules = ["required", { length = { min = 3, max = 12 } }]
+//! messages = { length = "name should be 3 to 12 characters" }
+//! bail = true
+//!
+//! [[fields]]
+//! field = "password"
+//! rules = [{ confirm = "$password2" }]
+//! ```
+
+use std::{collections::BTreeMap, error::Error, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{rule::RuleList, ValueMap};
+
+use super::{
+    registry::{Args, Param, Registry, RegistryError},
+    BuildError, IntoFieldName, MessageFn, MessageKey, Validator,
+};
+
+/// config document of validator
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorConfig {
+    /// fields are registered in order
+    #[serde(default)]
+    pub fields: Vec<FieldConfig>,
+
+    /// see [`Validator::bail`]
+    #[serde(default)]
+    pub bail: bool,
+
+    /// see [`Validator::lenient`]
+    #[serde(default)]
+    pub lenient: bool,
+}
+
+/// rules and messages of a field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldConfig {
+    /// field path, e.g. `address.zip` or `tags[*]`
+    pub field: String,
+
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+
+    /// messages keyed by rule name, e.g. `required` or `start_with#1`
+    #[serde(default)]
+    pub messages: BTreeMap<String, String>,
+
+    /// see [`RuleList::bail`]
+    #[serde(default)]
+    pub bail: bool,
+
+    /// see [`RuleList::nullable`]
+    #[serde(default)]
+    pub nullable: bool,
+}
+
+/// a rule is a name, e.g. `"required"`,
+/// or a map with the name and its arguments, e.g. `{ "length": [6, 12] }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RuleConfig {
+    Name(String),
+    WithArgs(BTreeMap<String, Param>),
+}
+
+impl RuleConfig {
+    fn parts(&self) -> Result<(&str, Args), String> {
+        match self {
+            RuleConfig::Name(name) => Ok((name, Args::new())),
+            RuleConfig::WithArgs(map) if map.len() == 1 => {
+                let (name, param) = map.iter().next().unwrap();
+                Ok((name, param.clone().into()))
+            }
+            RuleConfig::WithArgs(map) => Err(format!(
+                "a rule should be a map with one name, found {} names",
+                map.len()
+            )),
+        }
+    }
+}
+
+/// error of building validator from config, it points at the offending entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    entry: String,
+    kind: ConfigErrorKind,
+}
+
+/// the cause of [`ConfigError`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// the rule entry is neither a name nor a map with one name
+    RuleFormat(String),
+
+    /// the rule is not found, or its arguments are invalid
+    Registry(RegistryError),
+
+    /// the field path or message key is invalid
+    Build(BuildError),
+}
+
+impl ConfigError {
+    fn new<K: Into<ConfigErrorKind>>(entry: String, kind: K) -> Self {
+        Self {
+            entry,
+            kind: kind.into(),
+        }
+    }
+
+    /// path of the offending entry, e.g. `fields[1].rules[0]` or `fields[2].messages.required`
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    pub fn kind(&self) -> &ConfigErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ConfigErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigErrorKind::RuleFormat(reason) => reason.fmt(f),
+            ConfigErrorKind::Registry(err) => err.fmt(f),
+            ConfigErrorKind::Build(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid config entry `{}`: {}", self.entry, self.kind)
+    }
+}
+
+impl Error for ConfigError {}
+
+impl From<RegistryError> for ConfigErrorKind {
+    fn from(err: RegistryError) -> Self {
+        ConfigErrorKind::Registry(err)
+    }
+}
+
+impl From<BuildError> for ConfigErrorKind {
+    fn from(err: BuildError) -> Self {
+        ConfigErrorKind::Build(err)
+    }
+}
+
+impl<'c, M> Validator<'c, M> {
+    /// build validator from config, the rules are built by the registry,
+    /// see [`config`](self) module.
+    pub fn from_config(
+        config: &'c ValidatorConfig,
+        registry: &Registry<M>,
+    ) -> Result<Self, ConfigError>
+    where
+        M: From<String>,
+    {
+        let mut validator = Self::new();
+
+        for (index, field) in config.fields.iter().enumerate() {
+            let mut list = RuleList::<ValueMap, M>::default();
+
+            for (rule_index, rule) in field.rules.iter().enumerate() {
+                let entry = || format!("fields[{index}].rules[{rule_index}]");
+
+                let (name, args) = rule.parts().map_err(|reason| {
+                    ConfigError::new(entry(), ConfigErrorKind::RuleFormat(reason))
+                })?;
+                let rules = registry
+                    .build(name, &args)
+                    .map_err(|err| ConfigError::new(entry(), err))?;
+                list.extend(rules);
+            }
+            if field.bail {
+                list = list.bail();
+            }
+            if field.nullable {
+                list = list.nullable();
+            }
+
+            validator = validator
+                .try_rule(field.field.as_str(), list)
+                .map_err(|err| ConfigError::new(format!("fields[{index}].field"), err))?;
+
+            let names = field
+                .field
+                .as_str()
+                .into_field()
+                .unwrap_or_else(|never| match never {});
+            for (rule, message) in field.messages.iter() {
+                let key = MessageKey::new(names.clone(), rule);
+                validator.check_message(&key).map_err(|err| {
+                    ConfigError::new(format!("fields[{index}].messages.{rule}"), err)
+                })?;
+                validator
+                    .message
+                    .insert(key, MessageFn::convert(message.clone()));
+            }
+        }
+
+        if config.bail {
+            validator = validator.bail();
+        }
+        if config.lenient {
+            validator = validator.lenient();
+        }
+
+        Ok(validator)
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        available::{Message, MessageKind},
+        custom,
+    };
+
+    #[derive(Serialize)]
+    struct Input {
+        name: &'static str,
+        age: u8,
+        score: f32,
+        tags: Vec<&'static str>,
+    }
+
+    fn input() -> Input {
+        Input {
+            name: "admin",
+            age: 10,
+            score: 1.5,
+            tags: vec!["#rust", "go"],
+        }
+    }
+
+    fn not_admin(name: &mut String) -> Result<(), Message> {
+        if name == "admin" {
+            Err("admin is reserved".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry::new().register("not_admin", |_: &Args| Ok(custom(not_admin)))
+    }
+
+    fn check(config: &ValidatorConfig) {
+        let validator = Validator::from_config(config, &registry()).unwrap();
+        let err = validator.validate(input()).unwrap_err();
+
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::new(MessageKind::Length),
+                Message::from("the name is reserved")
+            ]
+        );
+        assert_eq!(err["age"], vec![Message::new(MessageKind::Range)]);
+        assert_eq!(err["score"], vec![Message::new(MessageKind::Range)]);
+        assert_eq!(
+            err["tags[1]"],
+            vec![Message::new(MessageKind::StartWith("#".into()))]
+        );
+        assert_eq!(err.len(), 4);
+    }
+
+    #[test]
+    fn test_json() {
+        let config: ValidatorConfig = serde_json::from_str(
+            r##"{
+                "fields": [
+                    {
+                        "field": "name",
+                        "rules": ["required", { "length": [6, 12] }, "not_admin"],
+                        "messages": { "not_admin": "the name is reserved" }
+                    },
+                    { "field": "age", "rules": [{ "range": { "min": 18 } }] },
+                    { "field": "score", "rules": [{ "range": [0, 1.0] }] },
+                    { "field": "tags[*]", "rules": [{ "start_with": "#" }] }
+                ]
+            }"##,
+        )
+        .unwrap();
+        check(&config);
+    }
+
+    #[test]
+    fn test_toml() {
+        let config: ValidatorConfig = toml::from_str(
+            r##"
+            [[fields]]
+            field = "name"
+            rules = ["required", { length = [6, 12] }, "not_admin"]
+            messages = { not_admin = "the name is reserved" }
+
+            [[fields]]
+            field = "age"
+            rules = [{ range = { min = 18 } }]
+
+            [[fields]]
+            field = "score"
+            rules = [{ range = [0, 1.0] }]
+
+            [[fields]]
+            field = "tags[*]"
+            rules = [{ start_with = "#" }]
+            "##,
+        )
+        .unwrap();
+        check(&config);
+    }
+
+    #[test]
+    fn test_yaml() {
+        let config: ValidatorConfig = serde_yaml::from_str(
+            r##"
+            fields:
+              - field: name
+                rules:
+                  - required
+                  - length: [6, 12]
+                  - not_admin
+                messages:
+                  not_admin: the name is reserved
+              - field: age
+                rules:
+                  - range: { min: 18 }
+              - field: score
+                rules:
+                  - range: [0, 1.0]
+              - field: tags[*]
+                rules:
+                  - start_with: "#"
+            "##,
+        )
+        .unwrap();
+        check(&config);
+    }
+
+    #[test]
+    fn test_settings() {
+        let config: ValidatorConfig = serde_json::from_str(
+            r#"{
+                "bail": true,
+                "fields": [
+                    { "field": "name", "rules": ["required", { "length": 3 }, "not_admin"] },
+                    { "field": "nickname", "rules": ["required"], "nullable": true }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let validator = Validator::from_config(&config, &registry()).unwrap();
+        assert!(validator.is_bail());
+
+        let err = validator.validate(input()).unwrap_err();
+        assert_eq!(err.total(), 1);
+        assert_eq!(err["name"], vec![Message::new(MessageKind::Length)]);
+    }
+
+    #[test]
+    fn test_big_range() {
+        // 2^53 + 1 is not exact as `f64`
+        let config: ValidatorConfig = serde_json::from_str(
+            r#"{ "fields": [{ "field": "0", "rules": [{ "range": [9007199254740993, 1e20] }] }] }"#,
+        )
+        .unwrap();
+        let validator = Validator::from_config(&config, &registry()).unwrap();
+
+        let err = validator.validate((9007199254740992_u64,)).unwrap_err();
+        assert_eq!(err["0"], vec![Message::new(MessageKind::Range)]);
+        validator.validate((9007199254740993_u64,)).unwrap();
+        validator.validate((u64::MAX,)).unwrap();
+        validator.validate((1e20,)).unwrap();
+    }
+
+    #[test]
+    fn test_conditional() {
+        let config: ValidatorConfig = serde_json::from_str(
+            r#"{
+                "fields": [
+                    { "field": "nickname", "rules": [{ "required_if": ["$age", 10] }] },
+                    { "field": "title", "rules": [{ "exclude_if": ["$score", 1.5] }, "required"] },
+                    { "field": "name", "rules": [{ "regex": "^[a-z]+$" }, { "lt": "$tags" }] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let validator = Validator::from_config(&config, &registry()).unwrap();
+
+        let err = validator.validate(input()).unwrap_err();
+        assert_eq!(
+            err["nickname"],
+            vec![Message::new(MessageKind::RequiredIf(
+                "age".into(),
+                "10".into()
+            ))]
+        );
+        assert_eq!(
+            err["name"],
+            vec![Message::new(MessageKind::Compare(
+                "less".into(),
+                "tags".into()
+            ))]
+        );
+        assert_eq!(err.len(), 2);
+
+        // `$$` is escaped `$`, it is not a field
+        let config: ValidatorConfig = serde_json::from_str(
+            r#"{ "fields": [{ "field": "name", "rules": [{ "start_with": "$$" }] }] }"#,
+        )
+        .unwrap();
+        let validator = Validator::from_config(&config, &registry()).unwrap();
+        assert_eq!(
+            validator.validate(input()).unwrap_err()["name"],
+            vec![Message::new(MessageKind::StartWith("$".into()))]
+        );
+        assert_eq!(
+            serde_json::to_string(&config.fields[0].rules).unwrap(),
+            r#"[{"start_with":"$$"}]"#
+        );
+    }
+
+    fn error(config: &str) -> ConfigError {
+        let config: ValidatorConfig = serde_json::from_str(config).unwrap();
+        Validator::from_config(&config, &registry()).err().unwrap()
+    }
+
+    #[test]
+    fn test_error() {
+        let err = error(
+            r#"{ "fields": [
+                { "field": "name", "rules": ["required"] },
+                { "field": "age", "rules": ["required", "requird"] }
+            ] }"#,
+        );
+        assert_eq!(err.entry(), "fields[1].rules[1]");
+        assert_eq!(
+            err.kind(),
+            &ConfigErrorKind::Registry(RegistryError::UnknownRule("requird".into()))
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid config entry `fields[1].rules[1]`: unknown rule `requird`"
+        );
+
+        let err = error(r#"{ "fields": [{ "field": "name", "rules": [{ "length": "foo" }] }] }"#);
+        assert_eq!(err.entry(), "fields[0].rules[0]");
+        assert!(matches!(
+            err.kind(),
+            ConfigErrorKind::Registry(RegistryError::InvalidArgs { rule, .. }) if rule == "length"
+        ));
+
+        let err = error(r#"{ "fields": [{ "field": "name", "rules": [{ "regex": "(" }] }] }"#);
+        assert_eq!(err.entry(), "fields[0].rules[0]");
+
+        let err = error(r#"{ "fields": [{ "field": "name", "rules": [{ "gt": 1 }] }] }"#);
+        assert_eq!(err.entry(), "fields[0].rules[0]");
+
+        let err = error(
+            r#"{ "fields": [{ "field": "name", "rules": [{ "required": [], "email": [] }] }] }"#,
+        );
+        assert!(matches!(err.kind(), ConfigErrorKind::RuleFormat(_)));
+
+        let err = error(r#"{ "fields": [{ "field": "name.", "rules": ["required"] }] }"#);
+        assert_eq!(err.entry(), "fields[0].field");
+        assert!(matches!(
+            err.kind(),
+            ConfigErrorKind::Build(BuildError::Field { .. })
+        ));
+
+        let err = error(
+            r#"{ "fields": [{ "field": "name", "rules": ["required"], "messages": { "email": "" } }] }"#,
+        );
+        assert_eq!(err.entry(), "fields[0].messages.email");
+        assert!(matches!(
+            err.kind(),
+            ConfigErrorKind::Build(BuildError::UnknownRule { .. })
+        ));
+
+        assert!(serde_json::from_str::<ValidatorConfig>(r#"{ "feilds": [] }"#).is_err());
+    }
+}
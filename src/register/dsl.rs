@@ -0,0 +1,520 @@
+//! # Pipe-string rules
+//!
+//! rules of a field are declared by a string, e.g. `"required|length:6,12|confirm:$password"`,
+//! the rule names are resolved by [`Registry`], so the rules registered by users are available.
+//!
+//! ```rust
+//! # use serde::Serialize;
+//! # use valitron::{available::Message, register::registry::Registry, rule::RuleList, Validator, ValueMap};
+//! let registry = Registry::<Message>::new();
+//!
+//! let validator = Validator::new()
+//!     .rule("name", registry.parse("required|length:3,12").unwrap())
+//!     .rule("password2", registry.parse("confirm:$password").unwrap())
+//!     // the built-in rules are available by `str::parse`
+//!     .rule("tag", "start_with:\"#\"".parse::<RuleList<ValueMap, Message>>().unwrap());
+//!
+//! #[derive(Serialize)]
+//! struct Input {
+//!     name: &'static str,
+//!     password: &'static str,
+//!     password2: &'static str,
+//!     tag: &'static str,
+//! }
+//!
+//! let err = validator
+//!     .validate(Input { name: "ab", password: "foo", password2: "bar", tag: "rust" })
+//!     .unwrap_err();
+//! assert_eq!(err.len(), 3);
+//!
+//! let err = registry.parse("required|lenght:3,12").unwrap_err();
+//! assert_eq!(err.position(), 9);
+//! assert_eq!(err.to_string(), "unknown rule `lenght` at position 9");
+//! ```
+//!
+//! ## Syntax
+//!
+//! - rules are separated by `|`, a rule is a name, or a name with arguments after `:`,
+//!   the arguments are separated by `,`, e.g. `length:6,12`
+//! - `true` and `false` are booleans, `6` and `-1.5` are numbers, others are strings
+//! - an argument in double quotes is always string, and it may contain `|` `,` and `:`,
+//!   `\"` and `\\` are escaped quote and backslash, e.g. `start_with:"a,b"`
+//! - `$password` refers to another field, e.g. `confirm:$password` or `gt:$items[0].price`,
+//!   the field path is checked when it is parsed
+//! - whitespace around names and arguments is ignored
+//!
+//! the arguments are positional, see the table of [`registry`] module.
+//!
+//! [`registry`]: super::registry
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{available::Message, rule::RuleList, ValueMap};
+
+use super::{
+    registry::{Args, Param, Registry, RegistryError},
+    BuildError, FieldNames,
+};
+
+/// error of parsing pipe-string rules, it points at the offending position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+/// the cause of [`ParseError`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// the rule name is missing, e.g. `required||email` or `:6,12`
+    EmptyRule,
+
+    /// the rule name starts with `0-9`
+    RuleName(String),
+
+    /// the argument is missing, e.g. `length:6,`
+    EmptyArg,
+
+    /// the quoted argument is not closed, e.g. `start_with:"foo`
+    UnclosedQuote,
+
+    /// the character is not expected here, e.g. `length:"6"7`
+    Unexpected(char),
+
+    /// the field path after `$` is invalid, e.g. `confirm:$a.`
+    Field { field: String, reason: String },
+
+    /// the rule is not found, or its arguments are invalid
+    Registry(RegistryError),
+}
+
+impl ParseError {
+    fn new<K: Into<ParseErrorKind>>(position: usize, kind: K) -> Self {
+        Self {
+            position,
+            kind: kind.into(),
+        }
+    }
+
+    /// byte offset of the offending token in source
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::EmptyRule => "expected a rule name".fmt(f),
+            ParseErrorKind::RuleName(name) => write!(f, "invalid rule name `{name}`"),
+            ParseErrorKind::EmptyArg => "expected an argument".fmt(f),
+            ParseErrorKind::UnclosedQuote => "unclosed quote".fmt(f),
+            ParseErrorKind::Unexpected(ch) => write!(f, "unexpected character `{ch}`"),
+            ParseErrorKind::Field { field, reason } => {
+                write!(f, "invalid field reference `${field}`: {reason}")
+            }
+            ParseErrorKind::Registry(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<RegistryError> for ParseErrorKind {
+    fn from(err: RegistryError) -> Self {
+        ParseErrorKind::Registry(err)
+    }
+}
+
+impl<M> Registry<M> {
+    /// parse pipe-string rules, see [`dsl`](self) module.
+    pub fn parse(&self, source: &str) -> Result<RuleList<ValueMap, M>, ParseError> {
+        let mut scanner = Scanner::new(source);
+        let mut list = RuleList::default();
+
+        loop {
+            scanner.skip_whitespace();
+            let start = scanner.position;
+            let name = scanner.name()?;
+            let args = scanner.args()?;
+
+            let rules = self
+                .build(name, &args)
+                .map_err(|err| ParseError::new(start, err))?;
+            list.extend(rules);
+
+            match scanner.bump() {
+                None => break Ok(list),
+                Some('|') => (),
+                Some(ch) => break Err(scanner.unexpected(ch)),
+            }
+        }
+    }
+}
+
+/// parse pipe-string rules by the built-in rules, see [`Registry::parse`]
+impl<M> FromStr for RuleList<ValueMap, M>
+where
+    M: From<Message> + 'static,
+{
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Registry::new().parse(source)
+    }
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.position += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// the error points at the character that has been bumped
+    fn unexpected(&self, ch: char) -> ParseError {
+        ParseError::new(
+            self.position - ch.len_utf8(),
+            ParseErrorKind::Unexpected(ch),
+        )
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(ch) if ch.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> &'a str {
+        let start = self.position;
+        while matches!(self.peek(), Some(ch) if predicate(ch)) {
+            self.bump();
+        }
+        &self.source[start..self.position]
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseError> {
+        let start = self.position;
+        let name = self.take_while(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+
+        match name.chars().next() {
+            None => Err(match self.peek() {
+                None | Some('|' | ':') => ParseError::new(start, ParseErrorKind::EmptyRule),
+                Some(ch) => ParseError::new(start, ParseErrorKind::Unexpected(ch)),
+            }),
+            Some(first) if first.is_ascii_digit() => Err(ParseError::new(
+                start,
+                ParseErrorKind::RuleName(name.to_owned()),
+            )),
+            Some(_) => {
+                self.skip_whitespace();
+                Ok(name)
+            }
+        }
+    }
+
+    /// the arguments after `:`, the following `|` is not consumed
+    fn args(&mut self) -> Result<Args, ParseError> {
+        match self.peek() {
+            Some(':') => {
+                self.bump();
+            }
+            None | Some('|') => return Ok(Args::new()),
+            Some(ch) => {
+                return Err(ParseError::new(
+                    self.position,
+                    ParseErrorKind::Unexpected(ch),
+                ))
+            }
+        }
+
+        let mut list = Vec::new();
+        loop {
+            self.skip_whitespace();
+            list.push(self.arg()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                }
+                None | Some('|') => break Ok(Args::positional(list)),
+                Some(ch) => {
+                    break Err(ParseError::new(
+                        self.position,
+                        ParseErrorKind::Unexpected(ch),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn arg(&mut self) -> Result<Param, ParseError> {
+        let start = self.position;
+        match self.peek() {
+            Some('"') => self.quoted(),
+            Some('$') => {
+                self.bump();
+                let field = self.bare();
+                if field.is_empty() {
+                    return Err(ParseError::new(
+                        start,
+                        ParseErrorKind::Field {
+                            field: String::new(),
+                            reason: "the field is empty".into(),
+                        },
+                    ));
+                }
+                if let Err(BuildError::Field { field, reason }) =
+                    FieldNames::new(field.to_owned()).check()
+                {
+                    return Err(ParseError::new(
+                        start,
+                        ParseErrorKind::Field { field, reason },
+                    ));
+                }
+                Ok(Param::Field(field.to_owned()))
+            }
+            _ => match self.bare() {
+                "" => Err(ParseError::new(start, ParseErrorKind::EmptyArg)),
+                "true" => Ok(Param::Bool(true)),
+                "false" => Ok(Param::Bool(false)),
+                text => Ok(number(text).unwrap_or_else(|| Param::String(text.to_owned()))),
+            },
+        }
+    }
+
+    /// the text until `,` or `|`, trailing whitespace is trimmed
+    fn bare(&mut self) -> &'a str {
+        self.take_while(|ch| ch != ',' && ch != '|').trim_end()
+    }
+
+    fn quoted(&mut self) -> Result<Param, ParseError> {
+        let start = self.position;
+        self.bump();
+
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => break Ok(Param::String(string)),
+                Some('\\') => match self.bump() {
+                    Some(ch) => string.push(ch),
+                    None => break Err(ParseError::new(start, ParseErrorKind::UnclosedQuote)),
+                },
+                Some(ch) => string.push(ch),
+                None => break Err(ParseError::new(start, ParseErrorKind::UnclosedQuote)),
+            }
+        }
+    }
+}
+
+/// `inf` and `NaN` are accepted by `f64::from_str`, but they are strings here
+fn number(text: &str) -> Option<Param> {
+    if !text.bytes().any(|b| b.is_ascii_digit())
+        || !text
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+    {
+        return None;
+    }
+
+    text.parse()
+        .map(Param::Int)
+        .or_else(|_| text.parse().map(Param::Float))
+        .ok()
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        available::{Message, MessageKind},
+        custom, Validator, Value,
+    };
+
+    fn not_admin(name: &mut String) -> Result<(), Message> {
+        if name == "admin" {
+            Err("admin is reserved".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry::new().register("not_admin", |_: &Args| Ok(custom(not_admin)))
+    }
+
+    #[test]
+    fn test_parse() {
+        let rules = registry()
+            .parse(" required | length: 6, 12 |not_admin|start_with:\"a|b\\\"\"")
+            .unwrap();
+        let info = rules.info();
+
+        assert_eq!(info.len(), 4);
+        assert_eq!(info[0].name(), "required");
+        assert_eq!(info[1].param("min"), Some(&Value::Uint64(6)));
+        assert_eq!(info[1].param("max"), Some(&Value::Uint64(12)));
+        assert_eq!(info[2].name(), "not_admin");
+        assert_eq!(
+            info[3].param("prefix"),
+            Some(&Value::String("a|b\"".into()))
+        );
+
+        let rules = registry()
+            .parse("range:-1.5,1e2|gt:$items[0].price")
+            .unwrap();
+        let info = rules.info();
+        assert_eq!(info[0].param("min"), Some(&Value::Float64((-1.5).into())));
+        assert_eq!(info[0].param("max"), Some(&Value::Float64(100.0.into())));
+        assert_eq!(
+            info[1].param("other"),
+            Some(&Value::String("items[0].price".into()))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        #[derive(Serialize)]
+        struct Input {
+            name: &'static str,
+            password: &'static str,
+            password2: &'static str,
+        }
+
+        let registry = registry();
+        let validator = Validator::new()
+            .rule(
+                "name",
+                registry.parse("required|length:6,12|not_admin").unwrap(),
+            )
+            .rule("password2", registry.parse("confirm:$password").unwrap())
+            .message([("name.not_admin", "the name is reserved")]);
+
+        let err = validator
+            .validate(Input {
+                name: "admin",
+                password: "foo",
+                password2: "bar",
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::new(MessageKind::Length),
+                Message::from("the name is reserved")
+            ]
+        );
+        assert_eq!(
+            err["password2"],
+            vec![Message::new(MessageKind::Confirm("password".into()))]
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let error = |source: &str| {
+            let err = registry().parse(source).unwrap_err();
+            (err.position(), err.kind().clone())
+        };
+
+        assert_eq!(error(""), (0, ParseErrorKind::EmptyRule));
+        assert_eq!(error("required||email"), (9, ParseErrorKind::EmptyRule));
+        assert_eq!(error("required|"), (9, ParseErrorKind::EmptyRule));
+        assert_eq!(
+            error("1required"),
+            (0, ParseErrorKind::RuleName("1required".into()))
+        );
+        assert_eq!(error("required-"), (8, ParseErrorKind::Unexpected('-')));
+        assert_eq!(error("length:6,"), (9, ParseErrorKind::EmptyArg));
+        assert_eq!(error("length:"), (7, ParseErrorKind::EmptyArg));
+        assert_eq!(
+            error("start_with:\"foo"),
+            (11, ParseErrorKind::UnclosedQuote)
+        );
+        assert_eq!(
+            error("start_with:\"foo\"bar"),
+            (16, ParseErrorKind::Unexpected('b'))
+        );
+        assert_eq!(
+            error("confirm:$"),
+            (
+                8,
+                ParseErrorKind::Field {
+                    field: String::new(),
+                    reason: "the field is empty".into()
+                }
+            )
+        );
+        assert!(matches!(
+            error("confirm:$a."),
+            (8, ParseErrorKind::Field { field, .. }) if field == "a."
+        ));
+        assert_eq!(
+            error("required|lenght"),
+            (
+                9,
+                ParseErrorKind::Registry(RegistryError::UnknownRule("lenght".into()))
+            )
+        );
+        assert!(matches!(
+            error("required|start_with:1"),
+            (9, ParseErrorKind::Registry(RegistryError::InvalidArgs { rule, .. })) if rule == "start_with"
+        ));
+
+        // field rules require `$`, and the values can not be fields
+        for (source, rule) in [
+            ("confirm:password", "confirm"),
+            ("gt:\"$min\"", "gt"),
+            ("required_if:$country,$other", "required_if"),
+            ("start_with:$prefix", "start_with"),
+        ] {
+            assert!(matches!(
+                error(source),
+                (0, ParseErrorKind::Registry(RegistryError::InvalidArgs { rule: name, .. })) if name == rule
+            ));
+        }
+
+        assert_eq!(
+            registry().parse("required|lenght").unwrap_err().to_string(),
+            "unknown rule `lenght` at position 9"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let rules: RuleList<ValueMap, Message> = "required|email".parse().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert!("not_admin".parse::<RuleList<ValueMap, Message>>().is_err());
+    }
+}
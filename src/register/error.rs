@@ -0,0 +1,54 @@
+use std::{convert::Infallible, error::Error, fmt::Display};
+
+/// error of building validator, it is returned by `try_rule` and `try_message`
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// the field format is invalid, e.g. `name.` or `[foo`
+    Field { field: String, reason: String },
+
+    /// the message key is not `field.rule` format
+    MessageKey(String),
+
+    /// the field of message key has not any rule
+    UnknownField(String),
+
+    /// the rule of message key is not found in the rules of field
+    UnknownRule { field: String, rule: String },
+
+    /// the rule name is not composed by `a-z` | `A-Z` | `0-9` | `_`, or it starts with `0-9`
+    RuleName { field: String, rule: String },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::Field { field, reason } => {
+                write!(f, "field `{field}` is invalid: {reason}")
+            }
+            BuildError::MessageKey(key) => {
+                write!(f, "message key `{key}` should be `field.rule` format")
+            }
+            BuildError::UnknownField(field) => {
+                write!(f, "the field \"{field}\" not found in validator")
+            }
+            BuildError::UnknownRule { field, rule } => {
+                write!(
+                    f,
+                    "rule \"{rule}\" is not found in rules of field \"{field}\""
+                )
+            }
+            BuildError::RuleName { field, rule } => {
+                write!(f, "invalid rule name \"{rule}\" of field \"{field}\"")
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+impl From<Infallible> for BuildError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
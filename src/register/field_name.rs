@@ -8,7 +8,7 @@ use serde::Serialize;
 
 use super::{
     lexer::{Cursor, Token, TokenKind},
-    MessageKey,
+    BuildError, MessageKey,
 };
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -19,6 +19,12 @@ pub enum FieldName {
 
     /// get `g` on enum A { Color{ r:u8, g:u8, b:u8}}
     StructVariant(String),
+
+    /// `[*]`, matching every item of array
+    Wildcard,
+
+    /// get `region` on `HashMap<String, T>` or `BTreeMap<String, T>`, e.g. `metadata["region"]`
+    MapKey(String),
 }
 
 impl FieldName {
@@ -26,6 +32,7 @@ impl FieldName {
         match self {
             FieldName::Literal(s) => s.as_str(),
             FieldName::StructVariant(s) => s.as_str(),
+            FieldName::MapKey(s) => s.as_str(),
             _ => "",
         }
     }
@@ -38,6 +45,8 @@ impl Display for FieldName {
             FieldName::Array(n) => n.fmt(f),
             FieldName::Tuple(n) => n.fmt(f),
             FieldName::StructVariant(s) => s.fmt(f),
+            FieldName::Wildcard => "*".fmt(f),
+            FieldName::MapKey(s) => s.fmt(f),
         }
     }
 }
@@ -68,6 +77,12 @@ fn names_to_string(vec: &[FieldName]) -> String {
                 string.push_str(s);
                 string.push(']');
             }
+            FieldName::Wildcard => string.push_str("[*]"),
+            FieldName::MapKey(s) => {
+                string.push_str("[\"");
+                string.push_str(s);
+                string.push_str("\"]");
+            }
         }
     }
     string
@@ -104,6 +119,48 @@ impl FieldNames {
     pub fn as_str(&self) -> &str {
         &self.string
     }
+
+    /// check the format of names, e.g. `name.` and `[foo` are invalid
+    pub(crate) fn check(&self) -> Result<(), BuildError> {
+        let mut parser = Parser::new(&self.string);
+        loop {
+            match parser.next_name() {
+                Ok(Some(_)) => (),
+                Ok(None) => break Ok(()),
+                Err(err) => {
+                    break Err(BuildError::Field {
+                        field: self.string.clone(),
+                        reason: err.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// check the names contain `[*]`, e.g. `items[*].name`
+    pub fn is_wildcard(&self) -> bool {
+        let mut parser = Parser::new(&self.string);
+        loop {
+            match parser.next_name() {
+                Ok(Some(FieldName::Wildcard)) => break true,
+                Ok(Some(_)) => (),
+                _ => break false,
+            }
+        }
+    }
+
+    /// re-root the child names under self, e.g. `address` joins `city` is `address.city`,
+    /// and joins `[0]` is `address[0]`
+    pub(crate) fn join(&self, child: &FieldNames) -> FieldNames {
+        if self.string.is_empty() {
+            return child.clone();
+        }
+        if child.string.starts_with('[') {
+            format!("{}{}", self.string, child.string).into()
+        } else {
+            format!("{}.{}", self.string, child.string).into()
+        }
+    }
 }
 
 impl From<Vec<FieldName>> for FieldNames {
@@ -143,7 +200,8 @@ impl From<&str> for FieldNames {
 
 /// Convert to FieldName trait
 pub trait IntoFieldName {
-    type Error: std::fmt::Display;
+    /// the error is converted to [`BuildError`] with the source of field
+    type Error: std::fmt::Display + Into<BuildError>;
     fn into_field(self) -> Result<FieldNames, Self::Error>;
 }
 
@@ -233,6 +291,8 @@ impl<'a> Parser<'a> {
                 self.parse_bracket().map(Some)
             }
             TokenKind::RightBracket => Err(ParserError::BracketRight),
+            TokenKind::Star => Err(ParserError::StarOutside),
+            TokenKind::Str => Err(ParserError::StrOutside),
             TokenKind::Index => {
                 let index_str;
                 (index_str, self.source) = self.source.split_at(token.len);
@@ -256,11 +316,59 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// parse `[0]` or `[abc]`
+    /// parse `[0]`, `[abc]`, `[*]` or `["abc"]`
     fn parse_bracket(&mut self) -> Result<FieldName, ParserError> {
         let mut peek = self.token.clone();
         let t = peek.advance();
         match t.kind() {
+            TokenKind::Str => {
+                if let Token {
+                    kind: TokenKind::RightBracket,
+                    ..
+                } = peek.advance()
+                {
+                    let str;
+                    (str, self.source) = self.source.split_at(t.len);
+                    let name = FieldName::MapKey(str[1..str.len() - 1].to_owned());
+
+                    // eat string
+                    self.token.advance();
+                    // eat `]`
+                    self.token.advance();
+                    self.source = &self.source[1..];
+
+                    if !(self.expect(TokenKind::Dot)
+                        || self.expect(TokenKind::LeftBracket)
+                        || self.expect(TokenKind::Eof))
+                    {
+                        return Err(ParserError::ArrayClose);
+                    }
+
+                    self.eat_dot()?;
+                    return Ok(name);
+                }
+            }
+            TokenKind::Star => {
+                if let Token {
+                    kind: TokenKind::RightBracket,
+                    ..
+                } = peek.advance()
+                {
+                    // eat `*` and `]`
+                    self.token.advance();
+                    self.token.advance();
+                    self.source = &self.source[2..];
+
+                    if !(self.expect(TokenKind::Dot)
+                        || self.expect(TokenKind::LeftBracket)
+                        || self.expect(TokenKind::Eof))
+                    {
+                        return Err(ParserError::ArrayClose);
+                    }
+                    self.eat_dot()?;
+                    return Ok(FieldName::Wildcard);
+                }
+            }
             TokenKind::Index => {
                 if let Token {
                     kind: TokenKind::RightBracket,
@@ -361,7 +469,7 @@ pub(crate) fn parse(source: &str) -> Result<Vec<FieldName>, ParserError> {
     }
 }
 
-pub fn parse_message(source: &str) -> Result<MessageKey, String> {
+pub fn parse_message(source: &str) -> Result<MessageKey<'_>, String> {
     let (name_str, string) = source
         .rsplit_once('.')
         .ok_or("not found message".to_owned())?;
@@ -372,6 +480,26 @@ pub fn parse_message(source: &str) -> Result<MessageKey, String> {
     ))
 }
 
+/// check the format of message key, it panics on the malformed key, e.g. `name` or `name..required`,
+/// it is used by [`messages!`](crate::messages) macro
+#[doc(hidden)]
+pub fn message_key(source: &str) -> &str {
+    let key = match parse_message(source) {
+        Ok(key) => key,
+        Err(err) => panic!("invalid message key `{source}`: {err}"),
+    };
+    if key.fields.as_str().is_empty() {
+        panic!("invalid message key `{source}`: the field is empty");
+    }
+    if let Err(err) = key.fields.check() {
+        panic!("invalid message key `{source}`: {err}");
+    }
+    if key.rule.is_empty() {
+        panic!("invalid message key `{source}`: the rule is empty");
+    }
+    source
+}
+
 #[derive(Debug)]
 pub(crate) enum ParserError {
     DotStart,
@@ -384,6 +512,10 @@ pub(crate) enum ParserError {
     BracketSyntaxError,
     DotIsLast,
     DotTieLeftBracket,
+    StarOutside,
+    StrOutside,
+    QuoteInKey,
+    CompositeKey,
 }
 
 impl Display for ParserError {
@@ -400,6 +532,10 @@ impl Display for ParserError {
             BracketSyntaxError => "bracket syntax error".fmt(f),
             DotIsLast => "`.` can not be end".fmt(f),
             DotTieLeftBracket => "after `.` can not be `[`".fmt(f),
+            StarOutside => "`*` only be used as `[*]`".fmt(f),
+            StrOutside => "string only be used as `[\"foo\"]`".fmt(f),
+            QuoteInKey => "map key can not contain `\"`".fmt(f),
+            CompositeKey => "map key should be a string, number, bool or char".fmt(f),
         }
     }
 }
@@ -459,4 +595,57 @@ fn test_parse() {
             FieldName::Tuple(0),
         ]
     );
+
+    let names = parse("items[*].name").unwrap();
+    assert_eq!(
+        names,
+        vec![
+            FieldName::Literal("items".into()),
+            FieldName::Wildcard,
+            FieldName::Literal("name".into()),
+        ]
+    );
+
+    let names = parse("[*][*]").unwrap();
+    assert_eq!(names, vec![FieldName::Wildcard, FieldName::Wildcard]);
+
+    let names = parse(r#"metadata["us-east.1"].name"#).unwrap();
+    assert_eq!(
+        names,
+        vec![
+            FieldName::Literal("metadata".into()),
+            FieldName::MapKey("us-east.1".into()),
+            FieldName::Literal("name".into()),
+        ]
+    );
+
+    let names = parse(r#"[""]"#).unwrap();
+    assert_eq!(names, vec![FieldName::MapKey("".into())]);
+
+    parse(r#"metadata."region""#).unwrap_err();
+    parse(r#"metadata["region"#).unwrap_err();
+    parse("items.*").unwrap_err();
+    parse("items[*]name").unwrap_err();
+    parse("items[*.name]").unwrap_err();
+}
+
+#[test]
+fn test_is_wildcard() {
+    assert!(FieldNames::new("items[*].name".into()).is_wildcard());
+    assert!(FieldNames::new("[*]".into()).is_wildcard());
+    assert!(!FieldNames::new("items[0].name".into()).is_wildcard());
+    assert!(!FieldNames::new("items".into()).is_wildcard());
+}
+
+#[test]
+fn test_join() {
+    let prefix = FieldNames::new("address".into());
+    assert_eq!(prefix.join(&"city".into()).as_str(), "address.city");
+    assert_eq!(prefix.join(&"0".into()).as_str(), "address.0");
+    assert_eq!(prefix.join(&"[0].name".into()).as_str(), "address[0].name");
+    assert_eq!(
+        prefix.join(&r#"["key"]"#.into()).as_str(),
+        r#"address["key"]"#
+    );
+    assert_eq!(FieldNames::default().join(&"city".into()).as_str(), "city");
 }
@@ -0,0 +1,112 @@
+//! list fields and rules of validator, it is used to generate docs and client-side hints
+
+use std::fmt::Debug;
+
+use crate::rule::RuleInfo;
+
+use super::{FieldNames, InnerValidator};
+
+/// description of a registered field, it is returned by [`InnerValidator::fields`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInfo<'a> {
+    names: &'a FieldNames,
+    label: Option<&'a str>,
+    rules: Vec<RuleInfo>,
+    is_bail: bool,
+    is_nullable: bool,
+}
+
+impl<'a> FieldInfo<'a> {
+    pub fn names(&self) -> &'a FieldNames {
+        self.names
+    }
+
+    /// see [`InnerValidator::label`]
+    pub fn label(&self) -> Option<&'a str> {
+        self.label
+    }
+
+    /// rules of the field in the declared order
+    pub fn rules(&self) -> &[RuleInfo] {
+        &self.rules
+    }
+
+    /// only the first message of the field is reported
+    pub fn is_bail(&self) -> bool {
+        self.is_bail
+    }
+
+    /// the rules are skipped when the field is `None`
+    pub fn is_nullable(&self) -> bool {
+        self.is_nullable
+    }
+}
+
+impl<M, List> InnerValidator<M, List> {
+    /// # List fields and rules
+    ///
+    /// the fields are listed in the order of registration, groups are not included.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use valitron::{available::{Length, Required, StartWith}, RuleExt, Validator, Value};
+    /// let validator = Validator::new()
+    ///     .rule("name", Required.and(StartWith("hi")).bail())
+    ///     .rule("password", Length(6..=12));
+    ///
+    /// let fields = validator.fields();
+    /// assert_eq!(fields[0].names().as_str(), "name");
+    /// assert!(fields[0].is_bail());
+    /// assert_eq!(fields[0].rules()[1].name(), "start_with");
+    /// assert_eq!(
+    ///     fields[0].rules()[1].param("prefix"),
+    ///     Some(&Value::String("hi".into()))
+    /// );
+    ///
+    /// let length = &fields[1].rules()[0];
+    /// assert_eq!(length.param("min"), Some(&Value::Uint64(6)));
+    /// assert_eq!(length.param("max"), Some(&Value::Uint64(12)));
+    /// # }
+    /// ```
+    pub fn fields(&self) -> Vec<FieldInfo<'_>> {
+        self.rules
+            .iter()
+            .map(|(names, rules)| FieldInfo {
+                names,
+                label: self.labels.get(names).map(String::as_str),
+                rules: rules.info(),
+                is_bail: rules.is_bail(),
+                is_nullable: rules.is_nullable(),
+            })
+            .collect()
+    }
+
+    /// when first validate error is encountered, right away return Err(message).
+    pub fn is_bail(&self) -> bool {
+        self.is_bail
+    }
+
+    /// the missing fields are skipped, see [`InnerValidator::lenient`]
+    pub fn is_lenient(&self) -> bool {
+        self.is_lenient
+    }
+}
+
+impl<M, List> Debug for InnerValidator<M, List>
+where
+    List: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Validator")
+            .field("rules", &self.rules)
+            .field("message", &self.message)
+            .field("is_bail", &self.is_bail)
+            .field("is_lenient", &self.is_lenient)
+            .field("deny_unknown_fields", &self.unknown_field.is_some())
+            .field("allowed_fields", &self.allowed_fields)
+            .field("labels", &self.labels)
+            .field("groups", &self.groups)
+            .finish()
+    }
+}
@@ -17,6 +17,12 @@ pub enum TokenKind {
     /// match `]`
     RightBracket,
 
+    /// match `*`
+    Star,
+
+    /// match `"foo"`, containing quotes
+    Str,
+
     /// undefined
     Undefined,
 
@@ -74,6 +80,17 @@ impl<'a> Cursor<'a> {
             '.' => (TokenKind::Dot, 1),
             '[' => (TokenKind::LeftBracket, 1),
             ']' => (TokenKind::RightBracket, 1),
+            '*' => (TokenKind::Star, 1),
+            '"' => {
+                let mut len = 1;
+                loop {
+                    match self.char.next() {
+                        Some((_, '"')) => break (TokenKind::Str, len + 1),
+                        Some((_, con)) => len += con.len_utf8(),
+                        None => break (TokenKind::Undefined, len),
+                    }
+                }
+            }
             'a'..='z' | 'A'..='Z' | '_' => {
                 let mut iter = self.char.clone().peekable();
                 let mut current_usize = start_usize;
@@ -158,5 +175,25 @@ mod test {
         assert_eq!(vec.advance().kind(), &TokenKind::Index);
         assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
         assert_eq!(vec.advance().kind(), &TokenKind::Eof);
+
+        let mut vec = Cursor::new(r#"abc["us-east.1"]"#);
+        assert_eq!(vec.advance().kind(), &TokenKind::Ident);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        let str = vec.advance();
+        assert_eq!(str.kind(), &TokenKind::Str);
+        assert!(str.len == 11);
+        assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Eof);
+
+        let mut vec = Cursor::new(r#"["abc"#);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Undefined);
+
+        let mut vec = Cursor::new("abc[*]");
+        assert_eq!(vec.advance().kind(), &TokenKind::Ident);
+        assert_eq!(vec.advance().kind(), &TokenKind::LeftBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Star);
+        assert_eq!(vec.advance().kind(), &TokenKind::RightBracket);
+        assert_eq!(vec.advance().kind(), &TokenKind::Eof);
     }
 }
@@ -1,18 +1,188 @@
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{rule::IntoRuleList, ser::Serializer, Validatable, Value, ValueMap};
 
-use super::{field_name, FieldNames, InnerValidator, IntoFieldName, MessageKey, ValidatorError};
+use super::{
+    field_name, BuildError, FieldNames, InnerValidator, IntoFieldName, MessageKey, ValidatorError,
+};
 
 pub trait IntoMessage {
     fn into_message(rule: &'static str, field: &FieldNames, value: &Value) -> Self;
+
+    /// build message with the parameters of rule and the label of field, e.g. `{min}` of `Length`,
+    /// it calls [`into_message`] by default.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use valitron::{available::Length, register::{FieldNames, IntoMessage, MessageInfo, ValidatorRefine}, Value};
+    /// #[derive(Debug)]
+    /// struct Msg(String);
+    ///
+    /// impl IntoMessage for Msg {
+    ///     fn into_message(rule: &'static str, field: &FieldNames, _value: &Value) -> Self {
+    ///         Msg(format!("{} is invalid by {rule}", field.as_str()))
+    ///     }
+    ///
+    ///     fn into_message_with(info: &MessageInfo<'_>) -> Self {
+    ///         match info.rule() {
+    ///             "length" => Msg(info.format("{label} should be {min} to {max} characters")),
+    ///             _ => Self::into_message(info.rule(), info.field(), info.value()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let validator = ValidatorRefine::new()
+    ///     .rule("0", Length(3..=12))
+    ///     .label("0", "Full name");
+    ///
+    /// let err = validator.validate::<_, Msg>(("ab",)).unwrap_err();
+    /// assert_eq!(err.get("0").unwrap()[0].0, "Full name should be 3 to 12 characters");
+    /// # }
+    /// ```
+    ///
+    /// [`into_message`]: IntoMessage::into_message
+    fn into_message_with(info: &MessageInfo<'_>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::into_message(info.rule(), info.field(), info.value())
+    }
+}
+
+/// the failed rule and field, it is used to build message, see [`IntoMessage::into_message_with`]
+#[derive(Debug, Clone, Copy)]
+pub struct MessageInfo<'a> {
+    rule: &'static str,
+    field: &'a FieldNames,
+    value: &'a Value,
+    params: &'a [(&'static str, Value)],
+    label: Option<&'a str>,
+}
+
+impl<'a> MessageInfo<'a> {
+    pub(crate) fn new(
+        rule: &'static str,
+        field: &'a FieldNames,
+        value: &'a Value,
+        params: &'a [(&'static str, Value)],
+        label: Option<&'a str>,
+    ) -> Self {
+        Self {
+            rule,
+            field,
+            value,
+            params,
+            label,
+        }
+    }
+
+    /// the name of failed rule, e.g. `length`
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    /// the path of field, the index of `[*]` is concrete, e.g. `items[1].name`
+    pub fn field(&self) -> &'a FieldNames {
+        self.field
+    }
+
+    pub fn value(&self) -> &'a Value {
+        self.value
+    }
+
+    /// parameters of the rule, see [`Rule::params`](crate::Rule::params)
+    pub fn params(&self) -> &'a [(&'static str, Value)] {
+        self.params
+    }
+
+    /// get a parameter by its name, e.g. `min`
+    pub fn param(&self, name: &str) -> Option<&'a Value> {
+        self.params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// the label of field, see [`InnerValidator::label`]
+    pub fn label(&self) -> Option<&'a str> {
+        self.label
+    }
+
+    /// # Replace placeholders of template
+    ///
+    /// - `{field}` is the path of field
+    /// - `{value}` is the value of field
+    /// - `{label}` is the label of field, it is the path when the field has not label
+    /// - others are the parameters of rule, e.g. `{min}` and `{max}` of `Length`,
+    ///   `{prefix}` of `StartWith`, `{other}` of `Confirm` and `Gt`
+    ///
+    /// the unknown placeholders are kept.
+    pub fn format(&self, template: &str) -> String {
+        let mut string = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            string.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            let replaced = after
+                .find('}')
+                .and_then(|end| Some((end, self.placeholder(&after[..end])?)));
+            match replaced {
+                Some((end, value)) => {
+                    string.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    string.push('{');
+                    rest = after;
+                }
+            }
+        }
+        string.push_str(rest);
+
+        string
+    }
+
+    fn placeholder(&self, name: &str) -> Option<String> {
+        match name {
+            "field" => Some(self.field.as_str().to_owned()),
+            "value" => Some(self.value.to_string()),
+            "label" => Some(self.label.unwrap_or(self.field.as_str()).to_owned()),
+            _ => self.param(name).map(ToString::to_string),
+        }
+    }
+}
+
+/// replace `{label}` of the messages of [`Validator`], the messages of `String` and [`Message`]
+/// are supported, other types are kept as they are.
+///
+/// [`Validator`]: crate::Validator
+/// [`Message`]: crate::available::Message
+pub(crate) fn replace_label<M: 'static>(mut message: M, label: &str) -> M {
+    let any: &mut dyn Any = &mut message;
+    if let Some(string) = any.downcast_mut::<String>() {
+        if string.contains("{label}") {
+            *string = string.replace("{label}", label);
+        }
+    }
+    #[cfg(feature = "full")]
+    if let Some(message) = any.downcast_mut::<crate::available::Message>() {
+        message.replace_label(label);
+    }
+    message
 }
 
 type CoreValidator<'v> = InnerValidator<String, HashMap<FieldNames, HashMap<&'v str, &'v str>>>;
 
 /// register a string message validator
+///
+/// the placeholders of messages are `{field}`, `{value}`, `{label}` and the parameters of rule,
+/// e.g. `{min}` of `Length`, see [`MessageInfo::format`].
+///
 /// ## This is an example:
 ///
 /// ```rust
@@ -103,28 +273,24 @@ impl<'v> ValidPhrase<'v> {
     }
 
     /// validate given data
-    pub fn validate<T>(self, data: T) -> Result<(), ValidatorError<String>>
+    pub fn validate<T>(&self, data: T) -> Result<(), ValidatorError<String>>
     where
         T: Serialize,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.0.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map).ok()
     }
 
     /// validate given data and can modify it
-    pub fn validate_mut<'de, T>(self, data: T) -> Result<T, ValidatorError<String>>
+    pub fn validate_mut<'de, T>(&self, data: T) -> Result<T, ValidatorError<String>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.0.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map)
@@ -133,40 +299,38 @@ impl<'v> ValidPhrase<'v> {
     }
 
     /// custom validation message
-    pub fn message<const N: usize>(mut self, list: [(&'v str, &'v str); N]) -> Self {
-        list.map(|(key_str, v)| {
-            let MessageKey { fields, rule } =
-                crate::panic_on_err!(field_name::parse_message(key_str));
-
-            debug_assert!(
-                self.0.rule_get(&fields).is_some(),
-                "the field \"{}\" not found in validator",
-                fields.as_str()
-            );
-            debug_assert!(
-                self.0.rule_get(&fields).unwrap().contains(rule),
-                "rule \"{rule}\" is not found in rules"
-            );
-
-            self.0
-                .message
-                .entry(fields)
-                .and_modify(|field| {
-                    field
-                        .entry(rule)
-                        .and_modify(|msg| {
-                            *msg = v;
-                        })
-                        .or_insert(v);
-                })
-                .or_insert({
-                    let mut map = HashMap::new();
-                    map.insert(rule, v);
-                    map
-                });
-        });
-
-        Self(self.0)
+    ///
+    /// # Panic
+    ///
+    /// When field or rule is not existing ,this will panic
+    pub fn message<const N: usize>(self, list: [(&'v str, &'v str); N]) -> Self {
+        crate::panic_on_err!(self.try_message(list))
+    }
+
+    /// custom validation message, it returns error instead of panic
+    pub fn try_message<I>(mut self, list: I) -> Result<Self, BuildError>
+    where
+        I: IntoIterator<Item = (&'v str, &'v str)>,
+    {
+        for (key_str, v) in list {
+            let MessageKey { fields, rule } = field_name::parse_message(key_str)
+                .map_err(|_| BuildError::MessageKey(key_str.to_owned()))?;
+
+            match self.0.rule_get(&fields) {
+                None => return Err(BuildError::UnknownField(fields.as_str().to_owned())),
+                Some(rules) if !rules.contains(rule) => {
+                    return Err(BuildError::UnknownRule {
+                        field: fields.as_str().to_owned(),
+                        rule: rule.to_owned(),
+                    })
+                }
+                Some(_) => (),
+            }
+
+            self.0.message.entry(fields).or_default().insert(rule, v);
+        }
+
+        Ok(self)
     }
 
     // pub fn map<M2>(self, f: fn(message: &'v str) -> M2) -> CoreValidator<'v, M2>
@@ -185,46 +349,122 @@ impl<'v> ValidPhrase<'v> {
         Self(self.0.rule(field, rule))
     }
 
+    /// register rules, it returns error instead of panic
+    pub fn try_rule<F, R>(self, field: F, rule: R) -> Result<Self, BuildError>
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, String>,
+    {
+        self.0.try_rule(field, rule).map(Self)
+    }
+
     /// when first validate error is encountered, right away return Err(message).
     pub fn bail(self) -> Self {
         Self(self.0.bail())
     }
 
-    fn inner_validate(self, value_map: &mut ValueMap) -> ValidatorError<String> {
-        let mut resp_message = ValidatorError::with_capacity(self.0.rules.len());
+    /// the human readable name of field, it is `{label}` of messages, see [`MessageInfo::format`]
+    pub fn label<F, L>(self, field: F, label: L) -> Self
+    where
+        F: IntoFieldName,
+        L: Into<String>,
+    {
+        Self(self.0.label(field, label))
+    }
 
-        let ValidPhrase(InnerValidator {
-            rules,
-            message,
-            is_bail,
-        }) = self;
+    /// skip the fields that are not found in the data
+    pub fn lenient(self) -> Self {
+        Self(self.0.lenient())
+    }
 
-        let default_map = HashMap::new();
+    /// report the fields without any rule, `{field}` of the message is replaced with the full path
+    pub fn deny_unknown_fields(self, message: &str) -> Self {
+        Self(self.0.deny_unknown_fields(message))
+    }
 
-        for (mut names, mut rules) in rules.into_iter() {
-            if is_bail {
-                rules.set_bail();
-            }
+    /// allow the fields without any rule, they are not reported as unknown
+    pub fn allow_fields<I, F>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: IntoFieldName,
+    {
+        Self(self.0.allow_fields(fields))
+    }
+
+    /// register rules under named group, see [`Validator::group`]
+    ///
+    /// [`Validator::group`]: crate::register::InnerValidator::group
+    pub fn group<G, F>(self, name: G, f: F) -> Self
+    where
+        G: Into<String>,
+        F: FnOnce(Self) -> Self,
+    {
+        Self(self.0.group(name, |group| f(Self(group)).0))
+    }
+
+    /// validate given data with selected groups, see [`Validator::validate_groups`]
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    ///
+    /// [`Validator::validate_groups`]: crate::Validator::validate_groups
+    pub fn validate_groups<'g, T, G>(
+        &self,
+        data: T,
+        groups: G,
+    ) -> Result<(), ValidatorError<String>>
+    where
+        T: Serialize,
+        G: IntoIterator<Item = &'g str>,
+    {
+        let value = data.serialize(Serializer).unwrap();
 
-            let msgs = message.get(&names).unwrap_or(&default_map);
+        let mut value_map = ValueMap::new(value);
 
-            value_map.index(names);
+        self.0
+            .iter_groups(&mut value_map, groups, Self::validate_core)
+            .ok()
+    }
 
-            let field_msg = rules.call_string_message(value_map, msgs);
+    /// validate given data with selected groups and can modify it
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    pub fn validate_mut_groups<'de, 'g, T, G>(
+        &self,
+        data: T,
+        groups: G,
+    ) -> Result<T, ValidatorError<String>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        G: IntoIterator<Item = &'g str>,
+    {
+        let value = data.serialize(Serializer).unwrap();
 
-            names = value_map.take_index();
+        let mut value_map = ValueMap::new(value);
 
-            resp_message.push(names, field_msg);
+        self.0
+            .iter_groups(&mut value_map, groups, Self::validate_core)
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
 
-            if is_bail && !resp_message.is_empty() {
-                resp_message.shrink_to(1);
-                return resp_message;
-            }
-        }
+    fn inner_validate(&self, value_map: &mut ValueMap) -> ValidatorError<String> {
+        Self::validate_core(&self.0, value_map)
+    }
 
-        resp_message.shrink_to_fit();
+    fn validate_core(
+        validator: &CoreValidator<'v>,
+        value_map: &mut ValueMap,
+    ) -> ValidatorError<String> {
+        let default_map = HashMap::new();
 
-        resp_message
+        validator.iter_validate(value_map, |rules, data, names, message, label| {
+            let msgs = message.get(names).unwrap_or(&default_map);
+            rules.call_string_message(data, msgs, label)
+        })
     }
 }
 
@@ -244,6 +484,22 @@ where
     }
 }
 
+impl<'v, T> Validatable<&ValidPhrase<'v>, ValidatorError<String>> for T
+where
+    T: Serialize,
+{
+    fn validate(&self, validator: &ValidPhrase<'v>) -> Result<(), ValidatorError<String>> {
+        validator.validate(self)
+    }
+
+    fn validate_mut<'de>(self, validator: &ValidPhrase<'v>) -> Result<Self, ValidatorError<String>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::marker::PhantomData;
@@ -265,11 +521,7 @@ mod tests {
         }
 
         fn call(&mut self, data: &mut Value) -> bool {
-            if *data == 8_i8 {
-                true
-            } else {
-                false
-            }
+            *data == 8_i8
         }
     }
 
@@ -289,6 +541,10 @@ mod tests {
             format!("this field must start with {}", self.0)
         }
 
+        fn params(&self) -> Vec<(&'static str, Value)> {
+            vec![("prefix", Value::String(self.0.into()))]
+        }
+
         const NAME: &'static str = "starts_with";
     }
 
@@ -368,6 +624,21 @@ mod tests {
         assert_eq!(msg[0], "10 is error value, 8 is true value");
     }
 
+    #[test]
+    fn wildcard() {
+        let list = vec![(8_i8, "foo"), (10_i8, "bar"), (9_i8, "baz")];
+
+        let validator = ValidPhrase::new()
+            .rule("[*].0", Required)
+            .message([("[*].0.required", "{field} is {value}")]);
+
+        let res = validator.validate(list).unwrap_err();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res["[1].0"], vec!["[1].0 is 10".to_string()]);
+        assert_eq!(res["[2].0"], vec!["[2].0 is 9".to_string()]);
+    }
+
     #[test]
     fn message() {
         let validator = ValidPhrase::new()
@@ -428,4 +699,70 @@ mod tests {
         assert_eq!(message_list3.len(), 1);
         assert_eq!(message_list3.get(&("field1".into())).unwrap().len(), 2);
     }
+
+    #[test]
+    fn params_and_label() {
+        let validator = ValidPhrase::new()
+            .rule("name", StartWith("foo"))
+            .rule("title", crate::any_of(StartWith("baz")))
+            .rule("tag", crate::all_of(StartWith("ba").and(StartWith("baz"))))
+            .label("name", "Full name")
+            .message([
+                (
+                    "name.starts_with",
+                    "{label} must start with {prefix}, {value} given",
+                ),
+                ("title.starts_with", "{label} must start with {prefix}"),
+                ("tag.starts_with", "{label} must start with {prefix}"),
+            ]);
+
+        #[derive(Serialize)]
+        struct Input {
+            name: &'static str,
+            title: &'static str,
+            tag: &'static str,
+        }
+
+        let res = validator
+            .validate(Input {
+                name: "bar",
+                title: "bar",
+                tag: "bar",
+            })
+            .unwrap_err();
+        assert_eq!(
+            res["name"],
+            vec!["Full name must start with foo, bar given"]
+        );
+        assert_eq!(res["title"], vec!["title must start with baz"]);
+        // the params are of the failed inner rule, not the first one with the same name
+        assert_eq!(res["tag"], vec!["tag must start with baz"]);
+
+        let res = ValidPhrase::new()
+            .rule("[*].0", Required)
+            .label("[*].0", "Number")
+            .message([("[*].0.required", "{label} of {field} is {value}, {unknown}")])
+            .validate(vec![(8_i8,), (10_i8,)])
+            .unwrap_err();
+        assert_eq!(res["[1].0"], vec!["Number of [1].0 is 10, {unknown}"]);
+    }
+
+    #[test]
+    fn format() {
+        let field = FieldNames::new("name".into());
+        let value = Value::String("ab".into());
+        let params = [("min", Value::Uint64(3)), ("max", Value::Uint64(12))];
+        let info = MessageInfo::new("length", &field, &value, &params, None);
+
+        assert_eq!(
+            info.format("{label} {value} should be {min} to {max}"),
+            "name ab should be 3 to 12"
+        );
+        assert_eq!(info.format("{ {field} }{"), "{ name }{");
+        assert_eq!(info.format("{field"), "{field");
+        assert_eq!(info.param("max"), Some(&Value::Uint64(12)));
+
+        let info = MessageInfo::new("length", &field, &value, &params, Some("Full name"));
+        assert_eq!(info.format("{label}"), "Full name");
+    }
 }
@@ -18,31 +18,53 @@
 //! [rules]: crate::available
 
 use std::{
-    collections::{
-        hash_map::{IntoIter, Iter, IterMut, Keys},
-        HashMap,
-    },
+    any::Any,
+    borrow::Cow,
+    collections::HashMap,
     error::Error,
     fmt::Display,
     hash::{Hash, Hasher},
     ops::Index,
+    sync::Arc,
+};
+
+use indexmap::{
+    map::{IntoIter, Iter, IterMut, Keys},
+    IndexMap,
 };
 
 use crate::{
     rule::{IntoRuleList, RuleList},
     ser::Serializer,
     value::ValueMap,
-    Value,
+    Rule, Value,
 };
 
+pub use error::BuildError;
+#[doc(hidden)]
+pub use field_name::message_key as __message_key;
 pub use field_name::{FieldName, FieldNames};
-pub(crate) use field_name::{IntoFieldName, Parser};
-pub use message::{IntoMessage, ValidPhrase};
+pub(crate) use field_name::{IntoFieldName, Parser, ParserError};
+pub use info::FieldInfo;
+use message::replace_label;
+pub use message::{IntoMessage, MessageInfo, ValidPhrase};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "full")]
+pub mod config;
+#[cfg(feature = "full")]
+pub mod dsl;
+mod error;
 mod field_name;
+mod info;
 mod lexer;
 mod message;
+#[cfg(feature = "full")]
+pub mod registry;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod string;
 #[cfg(test)]
 mod tests;
@@ -93,7 +115,58 @@ mod tests;
 ///     }
 /// }
 /// ```
-pub type Validator<'v, M> = InnerValidator<M, HashMap<MessageKey<'v>, M>>;
+///
+/// The validator is built once and can run many times by reference, it is `Send + Sync`
+/// when the message type is, so it can be stored in a `static` or shared with `Arc`.
+pub type Validator<'v, M> = InnerValidator<M, HashMap<MessageKey<'v>, MessageFn<'v, M>>>;
+
+/// custom message of [`Validator`], it makes a message on every failure,
+/// so the message type is not required to be `Clone`
+#[doc(hidden)]
+pub struct MessageFn<'v, M>(Arc<dyn Fn() -> M + Send + Sync + 'v>);
+
+impl<'v, M> MessageFn<'v, M> {
+    fn new(message: M) -> Self
+    where
+        M: Clone + Send + Sync + 'v,
+    {
+        Self(Arc::new(move || message.clone()))
+    }
+
+    /// the message is converted on every failure, so `M` is not required to be `Clone`,
+    /// e.g. the messages of config
+    #[cfg(feature = "full")]
+    fn convert<Msg>(message: Msg) -> Self
+    where
+        Msg: Into<M> + Clone + Send + Sync + 'v,
+    {
+        Self(Arc::new(move || message.clone().into()))
+    }
+
+    fn get(&self) -> M {
+        (self.0)()
+    }
+
+    fn map<M2>(self, f: fn(message: M) -> M2) -> MessageFn<'v, M2>
+    where
+        M: 'v,
+        M2: 'v,
+    {
+        MessageFn(Arc::new(move || f(self.get())))
+    }
+}
+
+impl<M> Clone for MessageFn<'_, M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<M: std::fmt::Debug> std::fmt::Debug for MessageFn<'_, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
 
 /// # A validator for build messages
 /// build message with rule name, field name and value
@@ -105,9 +178,20 @@ pub type ValidatorRefine<M> = InnerValidator<M, ()>;
 
 #[doc(hidden)]
 pub struct InnerValidator<M, List> {
-    rules: HashMap<FieldNames, RuleList<ValueMap, M>>,
+    /// keep the order of registration, it is the order of validating and error messages
+    rules: IndexMap<FieldNames, RuleList<ValueMap, M>>,
     message: List,
     is_bail: bool,
+    /// skip the fields that are not found in the data, instead of reporting them
+    is_lenient: bool,
+    /// it is enabled by `deny_unknown_fields`, the rule always fails with the message of unknown fields
+    unknown_field: Option<RuleList<ValueMap, M>>,
+    /// the fields are not reported as unknown, though they have not any rule
+    allowed_fields: Vec<FieldNames>,
+    /// human readable names of fields, they are `{label}` of message templates
+    labels: HashMap<FieldNames, String>,
+    /// named groups, every group has itself rules, messages and bail setting
+    groups: IndexMap<String, InnerValidator<M, List>>,
 }
 
 impl<M> Validator<'_, M> {
@@ -115,29 +199,38 @@ impl<M> Validator<'_, M> {
         Self::default()
     }
 
+    fn check_message(&self, MessageKey { fields, rule }: &MessageKey) -> Result<(), BuildError> {
+        match self.rule_get(fields) {
+            None => Err(BuildError::UnknownField(fields.as_str().to_owned())),
+            Some(rules) if !rules.contains(rule) => Err(BuildError::UnknownRule {
+                field: fields.as_str().to_owned(),
+                rule: rule.to_string(),
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+impl<M: 'static> Validator<'_, M> {
     /// run validate without modifiable
-    pub fn validate<T>(self, data: T) -> Result<(), ValidatorError<M>>
+    pub fn validate<T>(&self, data: T) -> Result<(), ValidatorError<M>>
     where
         T: Serialize,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map).ok()
     }
 
     /// run validate with modifiable
-    pub fn validate_mut<'de, T>(self, data: T) -> Result<T, ValidatorError<M>>
+    pub fn validate_mut<'de, T>(&self, data: T) -> Result<T, ValidatorError<M>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map)
@@ -145,39 +238,102 @@ impl<M> Validator<'_, M> {
             .map(|_| T::deserialize(value_map.value()).unwrap())
     }
 
-    fn inner_validate(self, value_map: &mut ValueMap) -> ValidatorError<M> {
-        fn handle_msg<M>(
-            rules: RuleList<ValueMap, M>,
+    /// run validate with external context, e.g. current user, tenant settings or a clock,
+    /// rules can get it by [`ValueMap::context`], closures can get it by [`custom_with_context`]
+    ///
+    /// ```rust
+    /// # use valitron::{Rule, Validator, Value, ValueMap};
+    /// struct Tenant {
+    ///     max_seats: u8,
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct PlanLimit;
+    ///
+    /// impl Rule for PlanLimit {
+    ///     type Message = &'static str;
+    ///
+    ///     const NAME: &'static str = "plan_limit";
+    ///
+    ///     fn message(&self) -> Self::Message {
+    ///         "the seats exceeds plan limit"
+    ///     }
+    ///
+    ///     fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+    ///         let max_seats = match data.context::<Tenant>() {
+    ///             Some(tenant) => tenant.max_seats,
+    ///             None => return false,
+    ///         };
+    ///         data.current().unwrap() <= &max_seats
+    ///     }
+    ///
+    ///     fn call(&mut self, _data: &mut Value) -> bool {
+    ///         unreachable!()
+    ///     }
+    /// }
+    ///
+    /// let validator = Validator::new().rule("0", PlanLimit);
+    ///
+    /// validator
+    ///     .validate_with_context((5_u8,), Tenant { max_seats: 10 })
+    ///     .unwrap();
+    /// validator
+    ///     .validate_with_context((15_u8,), Tenant { max_seats: 10 })
+    ///     .unwrap_err();
+    /// ```
+    ///
+    /// [`ValueMap::context`]: crate::ValueMap::context
+    /// [`custom_with_context`]: crate::custom_with_context
+    pub fn validate_with_context<T, C>(&self, data: T, context: C) -> Result<(), ValidatorError<M>>
+    where
+        T: Serialize,
+        C: Any + Send + Sync,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::with_context(value, context);
+
+        self.inner_validate(&mut value_map).ok()
+    }
+
+    /// run validate with modifiable and external context
+    pub fn validate_mut_with_context<'de, T, C>(
+        &self,
+        data: T,
+        context: C,
+    ) -> Result<T, ValidatorError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        C: Any + Send + Sync,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::with_context(value, context);
+
+        self.inner_validate(&mut value_map)
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
+
+    fn inner_validate(&self, value_map: &mut ValueMap) -> ValidatorError<M> {
+        fn handle_msg<M: 'static>(
+            rules: &RuleList<ValueMap, M>,
             value_map: &mut ValueMap,
-            message: &mut HashMap<MessageKey<'_>, M>,
+            names: &FieldNames,
+            message: &HashMap<MessageKey<'_>, MessageFn<'_, M>>,
+            label: Option<&str>,
         ) -> Vec<M> {
             rules
                 .call(value_map)
                 .into_iter()
-                .map(|(rule, msg)| {
-                    message
-                        .remove(&MessageKey::new(value_map.as_index().clone(), rule))
-                        .unwrap_or(msg)
+                .map(|(rule, instance, msg)| {
+                    let msg = get_message(message, names, rule, instance).unwrap_or(msg);
+                    replace_label(msg, label.unwrap_or(value_map.as_index().as_str()))
                 })
                 .collect()
         }
         self.iter_validate(value_map, handle_msg)
     }
-
-    fn exit_message(&self, MessageKey { fields, rule }: &MessageKey) -> bool {
-        debug_assert!(
-            self.rule_get(fields).is_some(),
-            "the field \"{}\" not found in validator",
-            fields.as_str()
-        );
-
-        debug_assert!(
-            self.rule_get(fields).unwrap().contains(rule),
-            "rule \"{rule}\" is not found in rules"
-        );
-
-        true
-    }
 }
 
 impl<M> ValidatorRefine<M> {
@@ -186,30 +342,26 @@ impl<M> ValidatorRefine<M> {
     }
 
     /// run validate without modifiable
-    pub fn validate<T, M2>(self, data: T) -> Result<(), ValidatorError<M2>>
+    pub fn validate<T, M2>(&self, data: T) -> Result<(), ValidatorError<M2>>
     where
         T: Serialize,
         M2: IntoMessage,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map).ok()
     }
 
     /// run validate with modifiable
-    pub fn validate_mut<'de, T, M2>(self, data: T) -> Result<T, ValidatorError<M2>>
+    pub fn validate_mut<'de, T, M2>(&self, data: T) -> Result<T, ValidatorError<M2>>
     where
         T: Serialize + serde::de::Deserialize<'de>,
         M2: IntoMessage,
     {
         let value = data.serialize(Serializer).unwrap();
 
-        debug_assert!(self.exist_field(&value));
-
         let mut value_map = ValueMap::new(value);
 
         self.inner_validate(&mut value_map)
@@ -218,11 +370,13 @@ impl<M> ValidatorRefine<M> {
     }
 
     /// inner creating message by field name and current value.
-    fn inner_validate<M2>(self, value_map: &mut ValueMap) -> ValidatorError<M2>
+    fn inner_validate<M2>(&self, value_map: &mut ValueMap) -> ValidatorError<M2>
     where
         M2: IntoMessage,
     {
-        self.iter_validate(value_map, |rules, data, _| rules.call_gen_message(data))
+        self.iter_validate(value_map, |rules, data, _, _, label| {
+            rules.call_gen_message(data, label)
+        })
     }
 }
 
@@ -236,21 +390,64 @@ impl<'v, M> Validator<'v, M> {
     ///
     /// e.g: `("name.required", "name is required")`
     ///
+    /// when the field has the same rule more than once, e.g. `StartWith("a").and(StartWith("b"))`,
+    /// `name.start_with#1` is the message of the second one, and `name.start_with` is used by the rest.
+    ///
     /// # Panic
     ///
-    /// When field or rule is not existing ,this will panic
-    pub fn message<const N: usize, Msg>(mut self, list: [(&'v str, Msg); N]) -> Self
+    /// When field or rule is not existing ,this will panic, [`try_message`] is the fallible version.
+    ///
+    /// the message is converted into `M` once, and cloned on every failure, so `M` should be `Clone`.
+    ///
+    /// [`try_message`]: Validator::try_message
+    pub fn message<const N: usize, Msg>(self, list: [(&'v str, Msg); N]) -> Self
+    where
+        Msg: Into<M>,
+        M: Clone + Send + Sync + 'v,
+    {
+        crate::panic_on_err!(self.try_message(list))
+    }
+
+    /// Custom validate error message, it returns error instead of panic,
+    /// it is useful when messages come from config.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use valitron::{available::Required, BuildError, Validator};
+    /// let validator = Validator::new()
+    ///     .rule("name", Required)
+    ///     .try_message([("name.required", "name is required")])
+    ///     .unwrap();
+    ///
+    /// let err = validator
+    ///     .try_message([("name.length", "name is too long")])
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     err,
+    ///     BuildError::UnknownRule {
+    ///         field: "name".into(),
+    ///         rule: "length".into()
+    ///     }
+    /// );
+    /// # }
+    /// ```
+    pub fn try_message<I, Msg>(mut self, list: I) -> Result<Self, BuildError>
     where
+        I: IntoIterator<Item = (&'v str, Msg)>,
         Msg: Into<M>,
+        M: Clone + Send + Sync + 'v,
     {
-        self.message.extend(list.map(|(key_str, v)| {
-            let msg_key = crate::panic_on_err!(field_name::parse_message(key_str));
+        for (key_str, v) in list {
+            let msg_key = field_name::parse_message(key_str)
+                .map_err(|_| BuildError::MessageKey(key_str.to_owned()))?;
 
-            debug_assert!(self.exit_message(&msg_key));
+            self.check_message(&msg_key)?;
 
-            (msg_key, v.into())
-        }));
-        self
+            self.message.insert(msg_key, MessageFn::new(v.into()));
+        }
+        Ok(self)
     }
 
     /// # convert `Validator<M1>` to `Validator<M2>`
@@ -263,6 +460,7 @@ impl<'v, M> Validator<'v, M> {
     ///     .map(MyError::from)
     ///     .message([("introduce.required", MyError::IntroduceRequired)]);
     ///
+    /// #[derive(Clone)]
     /// enum MyError {
     ///     IntroduceRequired,
     ///     NotReset,
@@ -293,22 +491,360 @@ impl<'v, M> Validator<'v, M> {
             message: self
                 .message
                 .into_iter()
-                .map(|(key, msg)| (key, f(msg)))
+                .map(|(key, msg)| (key, msg.map(f)))
                 .collect(),
             is_bail: self.is_bail,
+            is_lenient: self.is_lenient,
+            unknown_field: self.unknown_field.map(|list| list.map(f)),
+            allowed_fields: self.allowed_fields,
+            labels: self.labels,
+            groups: self
+                .groups
+                .into_iter()
+                .map(|(name, group)| (name, group.map(f)))
+                .collect(),
+        }
+    }
+}
+
+impl<'v, M> Validator<'v, M> {
+    /// # Mount other validator under the prefix
+    ///
+    /// every field and message key of the other validator is re-rooted under the prefix,
+    /// e.g. `city` is mounted as `shipping_address.city`, and errors are reported under the full path.
+    ///
+    /// rules of the same field are merged, groups of the other validator are mounted into the same named groups.
+    /// the bail setting of rules are kept, but the bail setting of the other validator is not effective.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use serde::Serialize;
+    /// # use valitron::{available::{Required, StartWith}, Validator};
+    /// #[derive(Serialize)]
+    /// struct Address {
+    ///     city: &'static str,
+    /// }
+    /// #[derive(Serialize)]
+    /// struct Order {
+    ///     shipping_address: Address,
+    ///     billing_address: Address,
+    /// }
+    ///
+    /// let address = Validator::new()
+    ///     .rule("city", Required)
+    ///     .message([("city.required", "city is required")]);
+    ///
+    /// let validator = Validator::new()
+    ///     .nest("shipping_address", address.clone())
+    ///     .nest("billing_address", address)
+    ///     .rule("billing_address.city", StartWith("N"));
+    ///
+    /// let order = Order {
+    ///     shipping_address: Address { city: "" },
+    ///     billing_address: Address { city: "Paris" },
+    /// };
+    /// let err = validator.validate(order).unwrap_err();
+    /// assert_eq!(
+    ///     err.get("shipping_address.city").unwrap()[0].to_string(),
+    ///     "city is required"
+    /// );
+    /// assert_eq!(err.get("billing_address.city").unwrap().len(), 1);
+    /// # }
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// - Prefix format error will be panic
+    pub fn nest<F>(mut self, prefix: F, other: Self) -> Self
+    where
+        F: IntoFieldName,
+    {
+        let prefix = crate::panic_on_err!(prefix.into_field());
+        crate::panic_on_err!(prefix.check());
+        self.merge_nested(&prefix, other);
+        self
+    }
+
+    fn merge_nested(&mut self, prefix: &FieldNames, other: Self) {
+        let Validator {
+            rules,
+            message,
+            allowed_fields,
+            labels,
+            groups,
+            ..
+        } = other;
+
+        self.allowed_fields
+            .extend(allowed_fields.iter().map(|names| prefix.join(names)));
+        self.labels.extend(
+            labels
+                .into_iter()
+                .map(|(names, label)| (prefix.join(&names), label)),
+        );
+
+        for (names, mut list) in rules {
+            if self.is_bail {
+                list.set_bail();
+            }
+            self.rules
+                .entry(prefix.join(&names))
+                .and_modify(|exist| exist.merge(&mut list))
+                .or_insert(list);
+        }
+
+        self.message.extend(
+            message
+                .into_iter()
+                .map(|(MessageKey { fields, rule }, msg)| {
+                    (MessageKey::new(prefix.join(&fields), rule), msg)
+                }),
+        );
+
+        let is_lenient = self.is_lenient;
+        for (name, group) in groups {
+            self.groups
+                .entry(name)
+                .or_insert_with(|| Self {
+                    is_lenient,
+                    ..Default::default()
+                })
+                .merge_nested(prefix, group);
         }
     }
 }
 
+impl<M, List> InnerValidator<M, List>
+where
+    List: Default,
+{
+    /// # Register rules under named group
+    ///
+    /// the group is a validator, it has itself rules, messages and bail setting,
+    /// they are only used by [`validate_groups`] when the group is selected.
+    /// calling it with the same name again will extend the group.
+    ///
+    /// rules registered out of groups are always validated, and before groups.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use serde::Serialize;
+    /// # use valitron::{available::{Length, Required}, RuleExt, Validator};
+    /// #[derive(Serialize)]
+    /// struct User {
+    ///     id: u32,
+    ///     name: &'static str,
+    ///     password: &'static str,
+    /// }
+    ///
+    /// let validator = Validator::new()
+    ///     .rule("name", Length(1..=20))
+    ///     .group("create", |v| {
+    ///         v.rule("password", Required.and(Length(6..=20)))
+    ///             .message([("password.required", "password is required")])
+    ///             .bail()
+    ///     })
+    ///     .group("update", |v| v.rule("id", Required));
+    ///
+    /// let user = User {
+    ///     id: 1,
+    ///     name: "foo",
+    ///     password: "",
+    /// };
+    ///
+    /// validator.validate_groups(&user, ["update"]).unwrap();
+    ///
+    /// let err = validator.validate_groups(&user, ["create"]).unwrap_err();
+    /// assert_eq!(err.get("password").unwrap().len(), 1);
+    /// assert_eq!(err.get("password").unwrap()[0].to_string(), "password is required");
+    /// # }
+    /// ```
+    ///
+    /// [`ValidatorRefine`] and [`ValidPhrase`] have groups as well.
+    ///
+    /// [`validate_groups`]: Validator::validate_groups
+    pub fn group<G, F>(mut self, name: G, f: F) -> Self
+    where
+        G: Into<String>,
+        F: FnOnce(Self) -> Self,
+    {
+        let is_lenient = self.is_lenient;
+        let group = self.groups.entry(name.into()).or_insert_with(|| Self {
+            is_lenient,
+            ..Default::default()
+        });
+        *group = f(std::mem::take(group));
+        self
+    }
+
+    /// # Panic
+    ///
+    /// when some group is not registered
+    fn select_groups<'g, G>(&self, groups: G) -> Vec<&Self>
+    where
+        G: IntoIterator<Item = &'g str>,
+    {
+        let names: Vec<&str> = groups.into_iter().collect();
+
+        for name in names.iter() {
+            assert!(
+                self.groups.contains_key(*name),
+                "the group \"{name}\" not found in validator"
+            );
+        }
+
+        self.groups
+            .iter()
+            .filter(|(name, _)| names.contains(&name.as_str()))
+            .map(|(_, group)| group)
+            .collect()
+    }
+
+    /// rules out of groups are validated first, then the selected groups in the order of registration
+    fn iter_groups<'g, G, F, T>(
+        &self,
+        value_map: &mut ValueMap,
+        groups: G,
+        validate: F,
+    ) -> ValidatorError<T>
+    where
+        G: IntoIterator<Item = &'g str>,
+        F: Fn(&Self, &mut ValueMap) -> ValidatorError<T>,
+    {
+        let groups = self.select_groups(groups);
+
+        let mut resp_message = validate(self, value_map);
+
+        if self.is_bail && !resp_message.is_empty() {
+            return resp_message;
+        }
+
+        for group in groups {
+            let group_message = validate(group, value_map);
+            let is_bail = group.is_bail && !group_message.is_empty();
+
+            resp_message.extend(group_message);
+
+            if is_bail {
+                break;
+            }
+        }
+
+        resp_message
+    }
+}
+
+impl<M: 'static> Validator<'_, M> {
+    /// run validate with selected groups, it is without modifiable
+    ///
+    /// rules out of groups are validated first, then the selected groups in the order of registration,
+    /// every group uses itself messages and bail setting.
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    pub fn validate_groups<'g, T, G>(&self, data: T, groups: G) -> Result<(), ValidatorError<M>>
+    where
+        T: Serialize,
+        G: IntoIterator<Item = &'g str>,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.iter_groups(&mut value_map, groups, Self::inner_validate)
+            .ok()
+    }
+
+    /// run validate with selected groups, it is with modifiable
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    pub fn validate_mut_groups<'de, 'g, T, G>(
+        &self,
+        data: T,
+        groups: G,
+    ) -> Result<T, ValidatorError<M>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        G: IntoIterator<Item = &'g str>,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.iter_groups(&mut value_map, groups, Self::inner_validate)
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
+}
+
+impl<M> ValidatorRefine<M> {
+    /// run validate with selected groups, it is without modifiable, see [`Validator::validate_groups`]
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    pub fn validate_groups<'g, T, G, M2>(
+        &self,
+        data: T,
+        groups: G,
+    ) -> Result<(), ValidatorError<M2>>
+    where
+        T: Serialize,
+        G: IntoIterator<Item = &'g str>,
+        M2: IntoMessage,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.iter_groups(&mut value_map, groups, Self::inner_validate)
+            .ok()
+    }
+
+    /// run validate with selected groups, it is with modifiable
+    ///
+    /// # Panic
+    ///
+    /// when some group is not registered
+    pub fn validate_mut_groups<'de, 'g, T, G, M2>(
+        &self,
+        data: T,
+        groups: G,
+    ) -> Result<T, ValidatorError<M2>>
+    where
+        T: Serialize + serde::de::Deserialize<'de>,
+        G: IntoIterator<Item = &'g str>,
+        M2: IntoMessage,
+    {
+        let value = data.serialize(Serializer).unwrap();
+
+        let mut value_map = ValueMap::new(value);
+
+        self.iter_groups(&mut value_map, groups, Self::inner_validate)
+            .ok()
+            .map(|_| T::deserialize(value_map.value()).unwrap())
+    }
+}
+
 impl<M, List> Default for InnerValidator<M, List>
 where
     List: Default,
 {
     fn default() -> Self {
         Self {
-            rules: HashMap::new(),
+            rules: IndexMap::new(),
             message: List::default(),
             is_bail: false,
+            is_lenient: false,
+            unknown_field: None,
+            allowed_fields: Vec::new(),
+            labels: HashMap::new(),
+            groups: IndexMap::new(),
         }
     }
 }
@@ -322,6 +858,11 @@ where
             rules: self.rules.clone(),
             message: self.message.clone(),
             is_bail: self.is_bail,
+            is_lenient: self.is_lenient,
+            unknown_field: self.unknown_field.clone(),
+            allowed_fields: self.allowed_fields.clone(),
+            labels: self.labels.clone(),
+            groups: self.groups.clone(),
         }
     }
 }
@@ -334,12 +875,21 @@ impl<M, List> InnerValidator<M, List> {
     /// - `0`,`1`.. used to matching tuple item or tuple struct field
     /// - `[0]`,`[1]` used to matching array item
     /// - `[foo]` used to matching struct variant, e.g. `enum Foo{ Color { r: u8, g: u8, b: u8 } }`
+    /// - `["foo"]` used to matching the value of `HashMap` or `BTreeMap` with string key,
+    ///   and `field1.foo` is also matching it when the key is a valid ident
+    /// - `[*]` used to matching every item of array, tuple or map, error messages is reported by concrete index,
+    ///   e.g. `items[*].name` is reported as `items[0].name`, `items[1].name` ..., items of tuple are
+    ///   reported as `pair.0`, `pair.1` ...
+    ///
+    /// `Some` is stepped into transparently, e.g. `profile.avatar.url` with `avatar: Option<Avatar>`
     ///
     /// fields support nest:
     /// - `field1.0`
     /// - `0.color`
     /// - `[12].1`
     /// - `foo.1[color]`
+    /// - `items[*].name`
+    /// - `metadata["region"]`
     /// - more combine
     ///
     /// fields's BNF:
@@ -348,22 +898,30 @@ impl<M, List> InnerValidator<M, List> {
     ///                          | <array_index>
     ///                          | <ident>
     ///                          | <struct_variant_index>
+    ///                          | <wildcard_index>
+    ///                          | <map_key_index>
     ///                          | <fields> '.' <tuple_index>
     ///                          | <fields> '.' <ident>
     ///                          | <fields> <array_index>
     ///                          | <fields> <struct_variant_index>
+    ///                          | <fields> <wildcard_index>
+    ///                          | <fields> <map_key_index>
     /// tuple_index            ::= <u8>
     /// array_index            ::= '[' <usize> ']'
     /// struct_variant_index   ::= '[' <ident> ']'
+    /// wildcard_index         ::= '[' '*' ']'
+    /// map_key_index          ::= '[' '"' <string> '"' ']'
     /// ```
     ///
     /// **Rule also support multiple formats:**
     /// - `RuleFoo`
     /// - `RuleFoo.and(RuleBar)` combineable
+    /// - `RuleFoo(a).and(RuleFoo(b))` both are kept, they are different instances of the rule
     /// - `custom(handler)` closure usage
     /// - `RuleFoo.custom(handler)` type and closure
     /// - `custom(handler).and(RuleFoo)` closure and type
     /// - `RuleFoo.and(RuleBar).bail()` when first validate error, immediately return error with one message.
+    /// - `RuleFoo.and(RuleBar).nullable()` when the field is `None`, or the field is absent because of `None` parent, skip the rules.
     ///
     /// *Available Rules*
     /// - [`Required`]
@@ -373,47 +931,282 @@ impl<M, List> InnerValidator<M, List> {
     /// - [`Range`]
     /// - customizable
     ///
+    /// Fields are validated and reported in the order of registration, and rules of
+    /// one field are executed in the declared order.
+    ///
     /// # Panic
     ///
     /// - Field format error will be panic
     /// - Invalid rule name will be panic
     ///
+    /// [`try_rule`] is the fallible version.
+    ///
     /// [`Required`]: crate::available::required
     /// [`StartWith`]: crate::available::start_with
     /// [`Confirm`]: crate::available::confirm
     /// [`Trim`]: crate::available::trim
     /// [`Range`]: crate::available::range
-    pub fn rule<F, R>(mut self, field: F, rule: R) -> Self
+    /// [`try_rule`]: InnerValidator::try_rule
+    pub fn rule<F, R>(self, field: F, rule: R) -> Self
     where
         F: IntoFieldName,
         R: IntoRuleList<ValueMap, M>,
     {
-        let names = crate::panic_on_err!(field.into_field());
+        crate::panic_on_err!(self.try_rule(field, rule))
+    }
+
+    /// Register rules, it returns error instead of panic, it is useful when fields come from config.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use valitron::{available::Required, BuildError, Validator};
+    /// let err = Validator::new().try_rule("name.", Required).err().unwrap();
+    /// assert!(matches!(err, BuildError::Field { .. }));
+    /// # }
+    /// ```
+    pub fn try_rule<F, R>(mut self, field: F, rule: R) -> Result<Self, BuildError>
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, M>,
+    {
+        let mut names = field.into_field().map_err(Into::into)?;
+        names.check()?;
+
         let mut rules = rule.into_list();
 
-        debug_assert!(rules.valid_name(), "invalid rule name");
+        if let Some(rule) = rules.invalid_name() {
+            return Err(BuildError::RuleName {
+                field: names.as_str().to_owned(),
+                rule: rule.to_owned(),
+            });
+        }
+
+        if rules.take_each() {
+            names = names.join(&FieldName::Wildcard.into());
+        }
+
+        if self.is_bail {
+            rules.set_bail();
+        }
 
         self.rules
             .entry(names)
             .and_modify(|list| list.merge(&mut rules))
             .or_insert(rules);
-        self
+        Ok(self)
+    }
+
+    /// # Register rules for every item of the field
+    ///
+    /// the field should be array, tuple or map, the rules are applied to every item of them,
+    /// and error messages are reported by concrete index, e.g. `tags[0]`, `tags[1]` ..., or `pair.0`, `pair.1` ... of tuple
+    ///
+    /// it is same as `rule("tags[*]", rule)` or `rule("tags", rule_list.each())`
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use valitron::{available::{Required, StartWith}, RuleExt, Validator};
+    /// #[derive(serde::Serialize)]
+    /// struct Post {
+    ///     tags: Vec<&'static str>,
+    /// }
+    ///
+    /// let validator = Validator::new().each("tags", Required.and(StartWith("#")));
+    ///
+    /// let post = Post {
+    ///     tags: vec!["#rust", "", "go"],
+    /// };
+    /// let err = validator.validate(post).unwrap_err();
+    /// assert_eq!(err.get("tags[1]").unwrap().len(), 2);
+    /// assert_eq!(err.get("tags[2]").unwrap().len(), 1);
+    /// # }
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// - Field format error will be panic
+    /// - Invalid rule name will be panic
+    pub fn each<F, R>(self, field: F, rule: R) -> Self
+    where
+        F: IntoFieldName,
+        R: IntoRuleList<ValueMap, M>,
+    {
+        self.rule(field, rule.into_list().each())
     }
 
     /// when first validate error is encountered, right away return Err(message).
     pub fn bail(mut self) -> Self {
         self.is_bail = true;
+        self.rules.values_mut().for_each(RuleList::set_bail);
         self
     }
 
-    fn exist_field(&self, value: &Value) -> bool {
-        for (field, _) in self.rules.iter() {
-            if value.get_with_names(field).is_none() {
-                panic!("field `{}` is not found", field.as_str());
-            }
+    /// # Skip missing fields
+    ///
+    /// by default, a field that is not found in the data is reported as an error,
+    /// the message is built by [`Rule::missing_message`] of the first failed rule,
+    /// e.g. [`MessageKind::Missing`] of the built-in rules, [`ClosureMessage::missing`] of the [`checked`] closures,
+    /// a plain closure is skipped.
+    ///
+    /// in the lenient mode, the missing fields are skipped, it is useful for partial updates.
+    /// it is effective to the groups.
+    ///
+    /// [`Rule::missing_message`]: crate::Rule::missing_message
+    /// [`MessageKind::Missing`]: crate::available::MessageKind::Missing
+    /// [`ClosureMessage::missing`]: crate::ClosureMessage::missing
+    /// [`checked`]: crate::checked
+    pub fn lenient(mut self) -> Self {
+        self.is_lenient = true;
+        self.groups
+            .values_mut()
+            .for_each(|group| group.is_lenient = true);
+        self
+    }
+
+    /// # Reject unknown fields
+    ///
+    /// every key of struct and map in the data, which has not any rule and is not allowed by [`allow_fields`],
+    /// is reported as an error with the message under its full path, e.g. `address.zip`.
+    ///
+    /// the message is registered as the `unknown_field` rule, so [`ValidatorRefine`] builds it by
+    /// [`IntoMessage`] with the rule name `unknown_field`.
+    /// fields of all groups are known, the rule of a field covers all of its children.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use serde::Serialize;
+    /// # use valitron::{available::{MessageKind, Required}, Validator};
+    /// #[derive(Serialize)]
+    /// struct User {
+    ///     name: &'static str,
+    ///     nickname: &'static str,
+    ///     age: u8,
+    /// }
+    ///
+    /// let validator = Validator::new()
+    ///     .rule("name", Required)
+    ///     .allow_fields(["age"])
+    ///     .deny_unknown_fields(MessageKind::UnknownField);
+    ///
+    /// let user = User {
+    ///     name: "foo",
+    ///     nickname: "bar",
+    ///     age: 18,
+    /// };
+    /// let err = validator.validate(user).unwrap_err();
+    /// assert_eq!(err.len(), 1);
+    /// assert_eq!(
+    ///     err.get("nickname").unwrap()[0].kind(),
+    ///     &MessageKind::UnknownField
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`allow_fields`]: InnerValidator::allow_fields
+    /// [`IntoMessage`]: message::IntoMessage
+    pub fn deny_unknown_fields<Msg>(mut self, message: Msg) -> Self
+    where
+        Msg: Into<M>,
+        M: Clone + Send + Sync + 'static,
+    {
+        self.unknown_field = Some(UnknownField(message.into()).into_list());
+        self
+    }
+
+    /// # Field label
+    ///
+    /// the human readable name of field, it is `{label}` of the message templates
+    /// of [`ValidPhrase`] and [`IntoMessage::into_message_with`], `{label}` is the field path
+    /// when the field has not label.
+    ///
+    /// `{label}` of the messages of [`Validator`] is replaced when the message is `String`
+    /// or [`Message`], other message types are returned as they are.
+    /// the labels are kept when it is converted into [`ValidatorRefine`],
+    /// and listed by [`InnerValidator::fields`].
+    ///
+    /// # Panic
+    ///
+    /// - Field format error will be panic
+    /// - the field is not registered, the label should be added after the rules of field
+    ///
+    /// [`Message`]: crate::available::Message
+    pub fn label<F, L>(mut self, field: F, label: L) -> Self
+    where
+        F: IntoFieldName,
+        L: Into<String>,
+    {
+        let names = crate::panic_on_err!(field.into_field());
+        crate::panic_on_err!(names.check());
+        if self.rule_get(&names).is_none() {
+            panic!("{}", BuildError::UnknownField(names.as_str().to_owned()));
+        }
+        self.labels.insert(names, label.into());
+        self
+    }
+
+    /// allow the fields without any rule, they are not reported by [`deny_unknown_fields`],
+    /// and all of their children are allowed too.
+    ///
+    /// # Panic
+    ///
+    /// - Field format error will be panic
+    ///
+    /// [`deny_unknown_fields`]: InnerValidator::deny_unknown_fields
+    pub fn allow_fields<I, F>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: IntoFieldName,
+    {
+        self.allowed_fields.extend(fields.into_iter().map(|field| {
+            let names = crate::panic_on_err!(field.into_field());
+            crate::panic_on_err!(names.check());
+            names
+        }));
+        self
+    }
+
+    /// the fields of rules and allowed fields, includes all groups
+    fn known_fields(&self) -> Vec<FieldNames> {
+        let mut fields: Vec<FieldNames> = self.rules.keys().cloned().collect();
+        fields.extend(self.allowed_fields.iter().cloned());
+        for group in self.groups.values() {
+            fields.extend(group.known_fields());
         }
+        fields
+    }
 
-        true
+    /// the fields to validate, `[*]` is expanded to every concrete index, and message is reported by them,
+    /// the skipped fields are not included.
+    fn fields_of(
+        &self,
+        rules: &RuleList<ValueMap, M>,
+        value: &Value,
+        names: &FieldNames,
+    ) -> Vec<FieldNames> {
+        if !names.is_wildcard() {
+            return if self.skip_field(rules, value, names) {
+                Vec::new()
+            } else {
+                vec![names.clone()]
+            };
+        }
+        match value.expand_names(names) {
+            Ok(fields) => fields
+                .into_iter()
+                .filter(|field| !self.skip_field(rules, value, field))
+                .collect(),
+            // some key of the data is not addressable, the field is reported as missing, instead of skipped
+            Err(_) => vec![names.clone()],
+        }
+    }
+
+    /// check the field should be skipped, it is missing in lenient mode, or it is nullable and `None`
+    fn skip_field(&self, rules: &RuleList<ValueMap, M>, value: &Value, field: &FieldNames) -> bool {
+        (self.is_lenient && value.get_with_names(field).is_none())
+            || (rules.is_nullable() && value.is_none_with_names(field))
     }
 
     #[inline(always)]
@@ -421,32 +1214,27 @@ impl<M, List> InnerValidator<M, List> {
         self.rules.get(names)
     }
 
-    fn iter_validate<F, T>(self, value_map: &mut ValueMap, handle_msg: F) -> ValidatorError<T>
+    fn iter_validate<F, T>(&self, value_map: &mut ValueMap, handle_msg: F) -> ValidatorError<T>
     where
-        F: Fn(RuleList<ValueMap, M>, &mut ValueMap, &mut List) -> Vec<T>,
+        F: Fn(&RuleList<ValueMap, M>, &mut ValueMap, &FieldNames, &List, Option<&str>) -> Vec<T>,
     {
         let mut resp_message = ValidatorError::with_capacity(self.rules.len());
 
-        let Self {
-            rules,
-            mut message,
-            is_bail,
-        } = self;
+        let mut walker = Walker::new(self);
+        while let Some(step) = walker.next(&value_map.value) {
+            value_map.index(step.field);
 
-        for (mut names, mut rules) in rules.into_iter() {
-            if is_bail {
-                rules.set_bail();
-            }
-
-            value_map.index(names);
+            let field_msg = handle_msg(
+                step.rules,
+                value_map,
+                &step.names,
+                &self.message,
+                step.label,
+            );
 
-            let field_msg = handle_msg(rules, value_map, &mut message);
+            resp_message.push(value_map.take_index(), field_msg);
 
-            names = value_map.take_index();
-
-            resp_message.push(names, field_msg);
-
-            if is_bail && !resp_message.is_empty() {
+            if self.is_bail && !resp_message.is_empty() {
                 resp_message.shrink_to(1);
                 return resp_message;
             }
@@ -463,13 +1251,138 @@ impl<M, List> InnerValidator<M, List> {
     }
 }
 
+/// walk the fields in the order of registration, then the unknown fields,
+/// it is shared by sync and async validating.
+///
+/// `[*]` of a field is expanded when the walker reaches the field, so it sees the changes of the previous rules.
+struct Walker<'a, M, List> {
+    validator: &'a InnerValidator<M, List>,
+    rules: indexmap::map::Iter<'a, FieldNames, RuleList<ValueMap, M>>,
+    current: Option<(
+        &'a FieldNames,
+        &'a RuleList<ValueMap, M>,
+        std::vec::IntoIter<FieldNames>,
+    )>,
+    unknown: Option<std::vec::IntoIter<FieldNames>>,
+}
+
+/// one field to validate
+struct Step<'a, M> {
+    /// the registered names, it is the key of messages and labels
+    names: Cow<'a, FieldNames>,
+    /// the concrete field, `[*]` is expanded
+    field: FieldNames,
+    rules: &'a RuleList<ValueMap, M>,
+    label: Option<&'a str>,
+}
+
+impl<'a, M, List> Walker<'a, M, List> {
+    fn new(validator: &'a InnerValidator<M, List>) -> Self {
+        Self {
+            validator,
+            rules: validator.rules.iter(),
+            current: None,
+            unknown: None,
+        }
+    }
+
+    fn next(&mut self, value: &Value) -> Option<Step<'a, M>> {
+        let validator = self.validator;
+        loop {
+            if let Some((names, rules, fields)) = &mut self.current {
+                if let Some(field) = fields.next() {
+                    return Some(Step {
+                        names: Cow::Borrowed(*names),
+                        field,
+                        rules: *rules,
+                        label: validator.labels.get(*names).map(String::as_str),
+                    });
+                }
+            }
+            match self.rules.next() {
+                Some((names, rules)) => {
+                    let fields = validator.fields_of(rules, value, names).into_iter();
+                    self.current = Some((names, rules, fields));
+                }
+                None => break,
+            }
+        }
+
+        // unknown fields are collected after all rules, they always fail
+        let rules = validator.unknown_field.as_ref()?;
+        let field = self
+            .unknown
+            .get_or_insert_with(|| value.unknown_names(&validator.known_fields()).into_iter())
+            .next()?;
+
+        Some(Step {
+            names: Cow::Owned(field.clone()),
+            field,
+            rules,
+            label: None,
+        })
+    }
+}
+
+/// find the custom message of the rule instance, e.g. `start_with#1`, then the rule, e.g. `start_with`
+fn get_message<M>(
+    message: &HashMap<MessageKey<'_>, MessageFn<'_, M>>,
+    names: &FieldNames,
+    rule: &str,
+    instance: usize,
+) -> Option<M> {
+    let instance = format!("{rule}#{instance}");
+    message
+        .get(&MessageKey::new(names.clone(), &instance))
+        .or_else(|| message.get(&MessageKey::new(names.clone(), rule)))
+        .map(MessageFn::get)
+}
+
+/// it is used by `deny_unknown_fields`, unknown fields always fail with the message
+#[derive(Clone)]
+struct UnknownField<M>(M);
+
+impl<M> Rule for UnknownField<M>
+where
+    M: Clone + Send + Sync,
+{
+    type Message = M;
+
+    const NAME: &'static str = "unknown_field";
+
+    fn message(&self) -> Self::Message {
+        self.0.clone()
+    }
+
+    fn call(&mut self, _data: &mut Value) -> bool {
+        false
+    }
+}
+
 impl<M> From<Validator<'_, M>> for ValidatorRefine<M> {
     fn from(value: Validator<'_, M>) -> Self {
-        let Validator { rules, is_bail, .. } = value;
+        let Validator {
+            rules,
+            is_bail,
+            is_lenient,
+            unknown_field,
+            allowed_fields,
+            labels,
+            groups,
+            ..
+        } = value;
         Self {
             rules,
             message: (),
             is_bail,
+            is_lenient,
+            unknown_field,
+            allowed_fields,
+            labels,
+            groups: groups
+                .into_iter()
+                .map(|(name, group)| (name, group.into()))
+                .collect(),
         }
     }
 }
@@ -502,6 +1415,23 @@ where
     }
 }
 
+impl<T, M> Validatable<&Validator<'_, M>, ValidatorError<M>> for T
+where
+    T: Serialize,
+    M: 'static,
+{
+    fn validate(&self, validator: &Validator<M>) -> Result<(), ValidatorError<M>> {
+        validator.validate(self)
+    }
+
+    fn validate_mut<'de>(self, validator: &Validator<M>) -> Result<Self, ValidatorError<M>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
+    }
+}
+
 impl<T, M, M2> Validatable<ValidatorRefine<M>, ValidatorError<M2>> for T
 where
     T: Serialize,
@@ -520,11 +1450,61 @@ where
     }
 }
 
+impl<T, M, M2> Validatable<&ValidatorRefine<M>, ValidatorError<M2>> for T
+where
+    T: Serialize,
+    M: 'static,
+    M2: IntoMessage,
+{
+    fn validate(&self, validator: &ValidatorRefine<M>) -> Result<(), ValidatorError<M2>> {
+        validator.validate(self)
+    }
+
+    fn validate_mut<'de>(self, validator: &ValidatorRefine<M>) -> Result<Self, ValidatorError<M2>>
+    where
+        Self: Deserialize<'de>,
+    {
+        validator.validate_mut(self)
+    }
+}
+
+/// the type with its own validator, it is implemented by `#[derive(Validate)]` with `derive` feature,
+/// and the type is validated by `data.validate(())`.
+///
+/// the validator is built once and kept in a static, e.g. `OnceLock`, so validating many times
+/// does not rebuild it.
+pub trait Validate {
+    type Message;
+
+    fn validator() -> &'static Validator<'static, Self::Message>;
+}
+
+impl<T> Validatable<(), ValidatorError<T::Message>> for T
+where
+    T: Validate + Serialize,
+    T::Message: 'static,
+{
+    fn validate(&self, _: ()) -> Result<(), ValidatorError<T::Message>> {
+        T::validator().validate(self)
+    }
+
+    fn validate_mut<'de>(self, _: ()) -> Result<Self, ValidatorError<T::Message>>
+    where
+        Self: Deserialize<'de>,
+    {
+        T::validator().validate_mut(self)
+    }
+}
+
 /// store validate error message
+///
+/// fields are ordered by registration of the validator, and messages of one field
+/// are ordered by its rules.
 pub type ValidatorError<M> = InnerValidatorError<FieldNames, M>;
 
 pub struct InnerValidatorError<F, M> {
-    message: HashMap<F, Vec<M>>,
+    /// the order of fields is the order of registration
+    message: IndexMap<F, Vec<M>>,
 }
 
 impl<F: Clone, M: Clone> Clone for InnerValidatorError<F, M> {
@@ -558,7 +1538,7 @@ impl<M> Index<&str> for ValidatorError<M> {
 
     fn index(&self, index: &str) -> &Self::Output {
         self.message
-            .get(&(index.into()))
+            .get(&FieldNames::from(index))
             .expect("this field is not found")
     }
 }
@@ -579,7 +1559,7 @@ where
     where
         S: serde::Serializer,
     {
-        self.message.serialize(serializer)
+        serializer.collect_map(&self.message)
     }
 }
 
@@ -602,12 +1582,12 @@ where
 {
     pub fn new() -> Self {
         Self {
-            message: HashMap::new(),
+            message: IndexMap::new(),
         }
     }
     fn with_capacity(capacity: usize) -> Self {
         Self {
-            message: HashMap::with_capacity(capacity),
+            message: IndexMap::with_capacity(capacity),
         }
     }
     fn shrink_to_fit(&mut self) {
@@ -661,6 +1641,13 @@ impl<M> ValidatorError<M> {
         }
     }
 
+    /// append messages of other error, the messages of same field are merged
+    fn extend(&mut self, other: Self) {
+        for (field_name, message) in other.message {
+            self.message.entry(field_name).or_default().extend(message);
+        }
+    }
+
     pub fn get<K: IntoFieldName>(&self, key: K) -> Option<&Vec<M>> {
         let k = key.into_field().ok()?;
         self.message.get(&k)
@@ -0,0 +1,546 @@
+//! # Rule registry
+//!
+//! map rule names to constructors of rules, the constructors receive [`Args`], it is used to
+//! build validator from config, see [`Validator::from_config`], and to parse pipe-string rules,
+//! see [`Registry::parse`].
+//!
+//! the built-in rules are registered by [`Registry::new`]:
+//!
+//! | name | arguments | rule |
+//! | --- | --- | --- |
+//! | `required` | | [`Required`] |
+//! | `email` | | [`Email`] |
+//! | `trim` | | [`Trim`] |
+//! | `prohibited` | | [`Prohibited`] |
+//! | `length` | `min`, `max` or an exact length | [`Length`] |
+//! | `range` | `min`, `max` | numbers in the range, inclusive |
+//! | `regex` | `pattern` | [`Regex`] |
+//! | `start_with`, `end_with`, `contains` | `value` | [`StartWith`], [`EndsWith`], [`Contains`] |
+//! | `confirm` | `$other` | [`Confirm`] |
+//! | `lt`, `elt`, `gt`, `egt` | `$other` | [`Lt`], [`Elt`], [`Gt`], [`Egt`] |
+//! | `required_if`, `required_unless`, `exclude_if` | `$other`, `value` | [`RequiredIf`], [`RequiredUnless`], [`ExcludeIf`] |
+//! | `required_with`, `required_without` | `$other` | [`RequiredWith`], [`RequiredWithout`] |
+//!
+//! arguments are named or positional, e.g. `{ "length": { "min": 6, "max": 12 } }`
+//! is same as `{ "length": [6, 12] }`.
+//!
+//! `$other` is a reference to other field, e.g. `{ "confirm": "$password" }`, it is [`Param::Field`],
+//! the other arguments can not be fields. a string starting with `$$` is a string starting with `$`.
+//!
+//! [`Validator::from_config`]: crate::register::Validator::from_config
+//! [`Required`]: crate::available::Required
+//! [`Email`]: crate::available::Email
+//! [`Trim`]: crate::available::Trim
+//! [`Prohibited`]: crate::available::Prohibited
+//! [`Length`]: crate::available::Length
+//! [`Regex`]: crate::available::Regex
+//! [`StartWith`]: crate::available::StartWith
+//! [`EndsWith`]: crate::available::EndsWith
+//! [`Contains`]: crate::available::Contains
+//! [`Confirm`]: crate::available::Confirm
+//! [`Lt`]: crate::available::Lt
+//! [`Elt`]: crate::available::Elt
+//! [`Gt`]: crate::available::Gt
+//! [`Egt`]: crate::available::Egt
+//! [`RequiredIf`]: crate::available::RequiredIf
+//! [`RequiredUnless`]: crate::available::RequiredUnless
+//! [`ExcludeIf`]: crate::available::ExcludeIf
+//! [`RequiredWith`]: crate::available::RequiredWith
+//! [`RequiredWithout`]: crate::available::RequiredWithout
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    available::{
+        condition::{number, Number},
+        Confirm, Contains, Egt, Elt, Email, EndsWith, ExcludeIf, Gt, Length, Lt, Message,
+        MessageKind, Prohibited, Regex, Required, RequiredIf, RequiredUnless, RequiredWith,
+        RequiredWithout, StartWith, Trim,
+    },
+    rule::{range_params, BoundParam, IntoRuleList, RuleList},
+    Rule, Value, ValueMap,
+};
+
+use super::FieldNames;
+
+/// argument value of rule, it is deserialized from config
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Param {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    #[serde(serialize_with = "serialize_string")]
+    String(String),
+    /// reference to other field, e.g. `$password`
+    #[serde(serialize_with = "serialize_field")]
+    Field(String),
+    List(Vec<Param>),
+    Map(BTreeMap<String, Param>),
+}
+
+fn serialize_string<S: Serializer>(string: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    if string.starts_with('$') {
+        serializer.collect_str(&format_args!("${string}"))
+    } else {
+        serializer.serialize_str(string)
+    }
+}
+
+fn serialize_field<S: Serializer>(field: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("${field}"))
+}
+
+/// the string starting with `$` is a field, and `$$` is escaped `$`
+impl<'de> Deserialize<'de> for Param {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            Int(i64),
+            Float(f64),
+            String(String),
+            List(Vec<Param>),
+            Map(BTreeMap<String, Param>),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Bool(b) => Param::Bool(b),
+            Repr::Int(n) => Param::Int(n),
+            Repr::Float(n) => Param::Float(n),
+            Repr::String(s) if s.starts_with("$$") => Param::String(s[1..].to_owned()),
+            Repr::String(s) => match s.strip_prefix('$') {
+                Some(field) => Param::Field(field.to_owned()),
+                None => Param::String(s),
+            },
+            Repr::List(list) => Param::List(list),
+            Repr::Map(map) => Param::Map(map),
+        })
+    }
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Param::Bool(b) => b.fmt(f),
+            Param::Int(n) => n.fmt(f),
+            Param::Float(n) => n.fmt(f),
+            Param::String(s) => s.fmt(f),
+            Param::Field(field) => write!(f, "${field}"),
+            Param::List(list) => {
+                let list: Vec<_> = list.iter().map(ToString::to_string).collect();
+                write!(f, "[{}]", list.join(", "))
+            }
+            Param::Map(map) => {
+                let map: Vec<_> = map.iter().map(|(k, v)| format!("{k}: {v}")).collect();
+                write!(f, "{{{}}}", map.join(", "))
+            }
+        }
+    }
+}
+
+impl From<&str> for Param {
+    fn from(value: &str) -> Self {
+        Param::String(value.to_owned())
+    }
+}
+
+impl From<i64> for Param {
+    fn from(value: i64) -> Self {
+        Param::Int(value)
+    }
+}
+
+impl From<f64> for Param {
+    fn from(value: f64) -> Self {
+        Param::Float(value)
+    }
+}
+
+impl From<bool> for Param {
+    fn from(value: bool) -> Self {
+        Param::Bool(value)
+    }
+}
+
+/// arguments of rule constructor, they are positional or named
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Args {
+    positional: Vec<Param>,
+    named: BTreeMap<String, Param>,
+}
+
+impl Args {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn positional(list: Vec<Param>) -> Self {
+        Self {
+            positional: list,
+            named: BTreeMap::new(),
+        }
+    }
+
+    pub fn named(map: BTreeMap<String, Param>) -> Self {
+        Self {
+            positional: Vec::new(),
+            named: map,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positional.is_empty() && self.named.is_empty()
+    }
+
+    /// get the argument by its name, or by its index when it is positional
+    pub fn get(&self, name: &str, index: usize) -> Option<&Param> {
+        self.named.get(name).or_else(|| self.positional.get(index))
+    }
+
+    /// get a string argument, it is error when the argument is not string
+    pub fn string(&self, name: &str, index: usize) -> Result<Option<&str>, String> {
+        match self.get(name, index) {
+            None => Ok(None),
+            Some(Param::String(s)) => Ok(Some(s)),
+            Some(other) => Err(format!("`{name}` should be a string, found `{other}`")),
+        }
+    }
+
+    /// get a string argument, it is error when the argument is not found
+    pub fn require_string(&self, name: &str, index: usize) -> Result<&str, String> {
+        self.string(name, index)?
+            .ok_or_else(|| format!("`{name}` is required"))
+    }
+
+    /// get a field argument, e.g. `$password`, it is error when the argument is not a field
+    /// or the field path is invalid
+    pub fn require_field(&self, name: &str, index: usize) -> Result<&str, String> {
+        match self.get(name, index) {
+            None => Err(format!("`{name}` is required")),
+            Some(Param::Field(field)) => {
+                FieldNames::new(field.clone())
+                    .check()
+                    .map_err(|err| err.to_string())?;
+                Ok(field)
+            }
+            Some(other) => Err(format!(
+                "`{name}` should be a field, e.g. `$password`, found `{other}`"
+            )),
+        }
+    }
+
+    /// get a number argument, integers are converted into `f64`
+    pub fn number(&self, name: &str, index: usize) -> Result<Option<f64>, String> {
+        match self.get(name, index) {
+            None => Ok(None),
+            Some(Param::Int(n)) => Ok(Some(*n as f64)),
+            Some(Param::Float(n)) => Ok(Some(*n)),
+            Some(other) => Err(format!("`{name}` should be a number, found `{other}`")),
+        }
+    }
+
+    /// get a non-negative integer argument
+    pub fn usize(&self, name: &str, index: usize) -> Result<Option<usize>, String> {
+        match self.get(name, index) {
+            None => Ok(None),
+            Some(Param::Int(n)) => usize::try_from(*n)
+                .map(Some)
+                .map_err(|_| format!("`{name}` should be a non-negative integer, found `{n}`")),
+            Some(other) => Err(format!(
+                "`{name}` should be a non-negative integer, found `{other}`"
+            )),
+        }
+    }
+}
+
+impl From<Param> for Args {
+    fn from(param: Param) -> Self {
+        match param {
+            Param::List(list) => Args::positional(list),
+            Param::Map(map) => Args::named(map),
+            other => Args::positional(vec![other]),
+        }
+    }
+}
+
+/// error of building rule by [`Registry`]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// the rule name is not registered
+    UnknownRule(String),
+
+    /// the arguments are rejected by the constructor of rule
+    InvalidArgs { rule: String, reason: String },
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::UnknownRule(rule) => write!(f, "unknown rule `{rule}`"),
+            RegistryError::InvalidArgs { rule, reason } => {
+                write!(f, "invalid arguments of rule `{rule}`: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+type Constructor<M> = Arc<dyn Fn(&Args) -> Result<RuleList<ValueMap, M>, String> + Send + Sync>;
+
+/// # Rule registry
+///
+/// ```rust
+/// # use valitron::{available::Message, custom, register::registry::{Args, Registry}};
+/// fn not_admin(name: &mut String) -> Result<(), Message> {
+///     if name == "admin" {
+///         Err("admin is reserved".into())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// let registry = Registry::new().register("not_admin", |_: &Args| Ok(custom(not_admin)));
+///
+/// let rules = registry.build("length", &Args::positional(vec![6.into(), 12.into()]));
+/// assert_eq!(rules.unwrap().len(), 1);
+///
+/// // the name of closure is the registered name, it is the message key
+/// let rules = registry.build("not_admin", &Args::new()).unwrap();
+/// assert_eq!(rules.info()[0].name(), "not_admin");
+///
+/// assert!(registry.build("lenght", &Args::new()).is_err());
+/// ```
+pub struct Registry<M = Message> {
+    rules: HashMap<&'static str, Constructor<M>>,
+}
+
+impl<M> Clone for Registry<M> {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl<M> Default for Registry<M>
+where
+    M: From<Message> + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Registry<M>
+where
+    M: From<Message> + 'static,
+{
+    /// the registry with built-in rules, their messages are converted by `From<Message>`
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for (name, constructor) in BUILT_IN {
+            registry.rules.insert(
+                name,
+                Arc::new(move |args: &Args| Ok(constructor(args)?.map(M::from))),
+            );
+        }
+        registry
+    }
+}
+
+impl<M> Registry<M> {
+    /// the registry without any rule
+    pub fn empty() -> Self {
+        Self {
+            rules: HashMap::new(),
+        }
+    }
+
+    /// register a rule constructor, the rule with the same name is replaced.
+    ///
+    /// when the constructor returns a closure, it is named by the `name`,
+    /// see [`RuleList::named`].
+    pub fn register<F, R>(mut self, name: &'static str, constructor: F) -> Self
+    where
+        F: Fn(&Args) -> Result<R, String> + Send + Sync + 'static,
+        R: IntoRuleList<ValueMap, M>,
+        M: 'static,
+    {
+        self.rules.insert(
+            name,
+            Arc::new(move |args: &Args| {
+                let list = constructor(args)?.into_list();
+                let info = list.info();
+                if matches!(info.as_slice(), [rule] if rule.is_custom() && rule.name() == "custom")
+                {
+                    Ok(list.named(name))
+                } else {
+                    Ok(list)
+                }
+            }),
+        );
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.rules.contains_key(name)
+    }
+
+    /// build rules by the registered name and arguments
+    pub fn build(&self, name: &str, args: &Args) -> Result<RuleList<ValueMap, M>, RegistryError> {
+        let constructor = self
+            .rules
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownRule(name.to_owned()))?;
+
+        constructor(args).map_err(|reason| RegistryError::InvalidArgs {
+            rule: name.to_owned(),
+            reason,
+        })
+    }
+}
+
+type BuiltIn = fn(&Args) -> Result<RuleList<ValueMap, Message>, String>;
+
+const BUILT_IN: [(&str, BuiltIn); 20] = [
+    ("required", |_| Ok(Required.into_list())),
+    ("email", |_| Ok(Email.into_list())),
+    ("trim", |_| Ok(Trim.into_list())),
+    ("prohibited", |_| Ok(Prohibited.into_list())),
+    ("length", length),
+    ("range", range),
+    ("regex", regex),
+    ("start_with", |args| {
+        Ok(StartWith(args.require_string("value", 0)?.to_owned()).into_list())
+    }),
+    ("end_with", |args| {
+        Ok(EndsWith(args.require_string("value", 0)?.to_owned()).into_list())
+    }),
+    ("contains", |args| {
+        Ok(Contains(args.require_string("value", 0)?.to_owned()).into_list())
+    }),
+    ("confirm", |args| Ok(Confirm(other(args)?).into_list())),
+    ("lt", |args| Ok(Lt(other(args)?).into_list())),
+    ("elt", |args| Ok(Elt(other(args)?).into_list())),
+    ("gt", |args| Ok(Gt(other(args)?).into_list())),
+    ("egt", |args| Ok(Egt(other(args)?).into_list())),
+    ("required_if", |args| {
+        Ok(RequiredIf(other(args)?, value(args)?).into_list())
+    }),
+    ("required_unless", |args| {
+        Ok(RequiredUnless(other(args)?, value(args)?).into_list())
+    }),
+    ("exclude_if", |args| {
+        Ok(ExcludeIf(other(args)?, value(args)?).into_list())
+    }),
+    ("required_with", |args| {
+        Ok(RequiredWith(other(args)?).into_list())
+    }),
+    ("required_without", |args| {
+        Ok(RequiredWithout(other(args)?).into_list())
+    }),
+];
+
+fn other(args: &Args) -> Result<String, String> {
+    args.require_field("other", 0).map(ToOwned::to_owned)
+}
+
+fn value(args: &Args) -> Result<Param, String> {
+    match args.get("value", 1) {
+        None => Err("`value` is required".to_owned()),
+        Some(Param::Field(field)) => {
+            Err(format!("`value` should not be a field, found `${field}`"))
+        }
+        Some(value) => Ok(value.clone()),
+    }
+}
+
+/// `length: 6` is exact length, `length: [6, 12]` or `length: {min: 6, max: 12}` is range
+fn length(args: &Args) -> Result<RuleList<ValueMap, Message>, String> {
+    let min = args.usize("min", 0)?;
+    let max = match args.positional.as_slice() {
+        [_] if args.named.is_empty() => min,
+        _ => args.usize("max", 1)?,
+    };
+    if min.is_none() && max.is_none() {
+        return Err("`min` or `max` is required".to_owned());
+    }
+    let bound = |n: Option<usize>| n.map_or(Bound::Unbounded, Bound::Included);
+
+    Ok(Length((bound(min), bound(max))).into_list())
+}
+
+fn range(args: &Args) -> Result<RuleList<ValueMap, Message>, String> {
+    let min = number_bound(args, "min", 0)?;
+    let max = number_bound(args, "max", 1)?;
+    if min == Bound::Unbounded && max == Bound::Unbounded {
+        return Err("`min` or `max` is required".to_owned());
+    }
+
+    Ok(NumberRange(min, max).into_list())
+}
+
+/// integers are kept exact, unlike [`Args::number`]
+fn number_bound(args: &Args, name: &str, index: usize) -> Result<Bound<Number>, String> {
+    match args.get(name, index) {
+        None => Ok(Bound::Unbounded),
+        Some(Param::Int(n)) => Ok(Bound::Included(Number::Int(*n as i128))),
+        Some(Param::Float(n)) => Ok(Bound::Included(Number::Float(*n))),
+        Some(other) => Err(format!("`{name}` should be a number, found `{other}`")),
+    }
+}
+
+fn regex(args: &Args) -> Result<RuleList<ValueMap, Message>, String> {
+    let pattern = args.require_string("pattern", 0)?;
+    regex::Regex::new(pattern).map_err(|err| err.to_string())?;
+
+    Ok(Regex::new(pattern.to_owned()).into_list())
+}
+
+/// the number type of [`Range`] is unknown in config, so numbers of any type are compared by their values
+///
+/// [`Range`]: crate::available::Range
+#[derive(Clone)]
+struct NumberRange(Bound<Number>, Bound<Number>);
+
+impl Rule for NumberRange {
+    type Message = Message;
+
+    const NAME: &'static str = "range";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Range)
+    }
+
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        range_params(&(self.0, self.1))
+    }
+
+    fn call(&mut self, data: &mut Value) -> bool {
+        number(data).is_some_and(|number| (self.0, self.1).contains(&number))
+    }
+}
+
+/// the bounds of config are integers of `i64` or floats
+impl BoundParam for Number {
+    fn into_param(self) -> Value {
+        match self {
+            Number::Int(n) => Value::Int64(n as i64),
+            Number::Float(n) => Value::Float64(n.into()),
+        }
+    }
+}
@@ -0,0 +1,652 @@
+//! export validator as [JSON Schema] (draft 2020-12), it requires `schema` feature.
+//!
+//! the built-in rules are mapped to keywords:
+//!
+//! | rule | keyword |
+//! | --- | --- |
+//! | `Required` | `required` of the parent object, items of array or tuple are not supported |
+//! | `Length` | `minLength`, `maxLength`, `minItems` and `maxItems` |
+//! | `Range` | `minimum`, `maximum`, `exclusiveMinimum` and `exclusiveMaximum` |
+//! | `Lt`, `Elt`, `Gt`, `Egt` with number | `exclusiveMaximum`, `maximum`, `exclusiveMinimum` and `minimum` |
+//! | `Regex` | `pattern` |
+//! | `StartWith`, `EndsWith`, `Contains` | `pattern` |
+//! | `Email` | `format: email` |
+//! | `Not` | `not` |
+//! | `any_of`, `all_of`, `xor`, `not` | `anyOf`, `allOf`, `oneOf`, `not` |
+//! | `Trim` | nothing |
+//!
+//! nested fields become nested `properties`, e.g. `address.zip`, and `[0]` or `0` becomes `prefixItems`.
+//! `[*]` becomes both `items` and `additionalProperties`, because the field may be an array or a map,
+//! unless the field is known as an array by `prefixItems`, or as an object by `properties`.
+//!
+//! custom closures, async rules, rules comparing with other fields (e.g. `Confirm`) and
+//! conditional rules can not be described by JSON Schema, they are reported by
+//! [`JsonSchema::non_exportable`].
+//!
+//! [JSON Schema]: https://json-schema.org/draft/2020-12/schema
+
+use serde_json::{json, Map, Number, Value as JsonValue};
+
+use crate::{rule::RuleInfo, Value};
+
+use super::{FieldName, FieldNames, InnerValidator, Parser};
+
+type Object = Map<String, JsonValue>;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// the exported document and the rules which are not exported
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSchema {
+    schema: JsonValue,
+    non_exportable: Vec<(FieldNames, &'static str)>,
+}
+
+impl JsonSchema {
+    pub fn schema(&self) -> &JsonValue {
+        &self.schema
+    }
+
+    pub fn into_schema(self) -> JsonValue {
+        self.schema
+    }
+
+    /// the field and name of rules which can not be described by JSON Schema,
+    /// they are skipped in the document.
+    pub fn non_exportable(&self) -> &[(FieldNames, &'static str)] {
+        &self.non_exportable
+    }
+
+    /// all of rules are exported
+    pub fn is_complete(&self) -> bool {
+        self.non_exportable.is_empty()
+    }
+}
+
+impl<M, List> InnerValidator<M, List> {
+    /// # Export as JSON Schema
+    ///
+    /// groups are not included, when [`deny_unknown_fields`] is enabled,
+    /// the objects which have not any rule are `additionalProperties: false`.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "full")]
+    /// # {
+    /// # use serde_json::json;
+    /// # use valitron::{available::{Email, Length, Message, Required}, RuleExt, Validator};
+    /// fn not_admin(name: &mut String) -> Result<(), Message> {
+    ///     Ok(())
+    /// }
+    ///
+    /// let validator = Validator::new()
+    ///     .rule("name", Required.and(Length(3..=12)).custom(not_admin))
+    ///     .rule("contact.email", Email);
+    ///
+    /// let schema = validator.json_schema();
+    /// assert_eq!(
+    ///     schema.schema(),
+    ///     &json!({
+    ///         "$schema": "https://json-schema.org/draft/2020-12/schema",
+    ///         "type": "object",
+    ///         "properties": {
+    ///             "name": { "minLength": 3, "maxLength": 12, "minItems": 3, "maxItems": 12 },
+    ///             "contact": {
+    ///                 "type": "object",
+    ///                 "properties": { "email": { "format": "email" } }
+    ///             }
+    ///         },
+    ///         "required": ["name"]
+    ///     })
+    /// );
+    /// assert_eq!(schema.non_exportable()[0].0.as_str(), "name");
+    /// assert_eq!(schema.non_exportable()[0].1, "custom");
+    /// # }
+    /// ```
+    ///
+    /// [`deny_unknown_fields`]: InnerValidator::deny_unknown_fields
+    pub fn json_schema(&self) -> JsonSchema {
+        let mut root = Object::new();
+        root.insert("$schema".into(), DRAFT.into());
+        let mut non_exportable = Vec::new();
+
+        for field in self.fields() {
+            let path = parse_path(field.names());
+            let Some((last, parents)) = path.split_last() else {
+                continue;
+            };
+
+            let mut required = false;
+            let mut fragment = Object::new();
+            for rule in field.rules() {
+                match rule_schema(rule) {
+                    Ok(Fragment::Required) => required = true,
+                    Ok(Fragment::Schema(schema)) => merge(&mut fragment, schema),
+                    Ok(Fragment::Empty) => (),
+                    Err(names) => non_exportable
+                        .extend(names.into_iter().map(|name| (field.names().clone(), name))),
+                }
+            }
+            if field.is_nullable() && !fragment.is_empty() {
+                let mut nullable = Object::new();
+                nullable.insert(
+                    "anyOf".into(),
+                    json!([{ "type": "null" }, JsonValue::Object(fragment)]),
+                );
+                fragment = nullable;
+            }
+
+            let parent = node_mut(&mut root, parents);
+            if required {
+                match property_key(last) {
+                    Some(key) => push_required(parent, key),
+                    // e.g. `items[0]` or `items[*]`, the items can not be required by name
+                    None => non_exportable.push((field.names().clone(), "required")),
+                }
+            }
+            merge(child_mut(parent, last), fragment);
+        }
+
+        if self.unknown_field.is_some() {
+            self.deny_additional(&mut root);
+        }
+        wildcard_map(&mut root);
+
+        JsonSchema {
+            schema: JsonValue::Object(root),
+            non_exportable,
+        }
+    }
+
+    /// the objects without rule are closed, the children of fields with rule are allowed
+    fn deny_additional(&self, root: &mut Object) {
+        let covered: Vec<Vec<FieldName>> = self
+            .rules
+            .keys()
+            .chain(self.allowed_fields.iter())
+            .map(parse_path)
+            .collect();
+
+        for path in covered.iter().filter(|path| !path.is_empty()) {
+            let (last, parents) = path.split_last().unwrap();
+            child_mut(node_mut(root, parents), last);
+        }
+
+        let mut prefixes: Vec<&[FieldName]> = Vec::new();
+        for path in covered.iter() {
+            for len in 0..path.len() {
+                let prefix = &path[..len];
+                if !prefixes.contains(&prefix) && !covered.iter().any(|item| item == prefix) {
+                    prefixes.push(prefix);
+                }
+            }
+        }
+
+        for prefix in prefixes {
+            let node = node_mut(root, prefix);
+            if node.contains_key("properties") {
+                node.insert("additionalProperties".into(), false.into());
+            }
+        }
+    }
+}
+
+enum Fragment {
+    Required,
+    Schema(Object),
+    Empty,
+}
+
+/// it returns the names of rules, which are not exportable
+fn rule_schema(rule: &RuleInfo) -> Result<Fragment, Vec<&'static str>> {
+    if rule.is_custom() || rule.is_async() {
+        return Err(vec![rule.name()]);
+    }
+
+    let unsupported = || Err(vec![rule.name()]);
+    let mut schema = Object::new();
+
+    match rule.name() {
+        "required" if rule.param("not").is_none() => return Ok(Fragment::Required),
+        "trim" => return Ok(Fragment::Empty),
+        "length" => {
+            for (param, keywords) in [
+                ("min", ["minLength", "minItems"]),
+                ("max", ["maxLength", "maxItems"]),
+            ] {
+                if let Some(value) = rule.param(param).and_then(json_number) {
+                    for keyword in keywords {
+                        schema.insert(keyword.into(), value.clone());
+                    }
+                }
+            }
+        }
+        "range" => {
+            for (param, keyword) in [
+                ("min", "minimum"),
+                ("max", "maximum"),
+                ("exclusive_min", "exclusiveMinimum"),
+                ("exclusive_max", "exclusiveMaximum"),
+            ] {
+                if let Some(value) = rule.param(param) {
+                    match json_number(value) {
+                        Some(value) => schema.insert(keyword.into(), value),
+                        // e.g. range of char
+                        None => return unsupported(),
+                    };
+                }
+            }
+        }
+        name @ ("lt" | "elt" | "gt" | "egt") => {
+            let Some(value) = rule.param("other").and_then(json_number) else {
+                // comparing with other field
+                return unsupported();
+            };
+            let keyword = match name {
+                "lt" => "exclusiveMaximum",
+                "elt" => "maximum",
+                "gt" => "exclusiveMinimum",
+                _ => "minimum",
+            };
+            schema.insert(keyword.into(), value);
+        }
+        "regex" => {
+            let Some(Value::String(pattern)) = rule.param("pattern") else {
+                return unsupported();
+            };
+            schema.insert("pattern".into(), pattern.as_str().into());
+        }
+        "start_with" | "end_with" | "contains" => {
+            let param = match rule.name() {
+                "start_with" => "prefix",
+                "end_with" => "suffix",
+                _ => "substring",
+            };
+            let Some(Value::String(text)) = rule.param(param) else {
+                return unsupported();
+            };
+            let text = escape(text);
+            let pattern = match rule.name() {
+                "start_with" => format!("^{text}"),
+                "end_with" => format!("{text}$"),
+                _ => text,
+            };
+            schema.insert("pattern".into(), pattern.into());
+        }
+        "email" => {
+            schema.insert("format".into(), "email".into());
+        }
+        name @ ("any_of" | "all_of" | "xor" | "not") => {
+            let mut list = Vec::new();
+            let mut names = Vec::new();
+            for inner in rule.inner() {
+                match rule_schema(inner) {
+                    Ok(Fragment::Schema(schema)) => list.push(JsonValue::Object(schema)),
+                    Ok(Fragment::Empty) => list.push(JsonValue::Object(Object::new())),
+                    // `required` can not be described in the sub-schema
+                    Ok(Fragment::Required) => names.push(inner.name()),
+                    Err(inner) => names.extend(inner),
+                }
+            }
+            if !names.is_empty() {
+                return Err(names);
+            }
+            match name {
+                "any_of" => schema.insert("anyOf".into(), list.into()),
+                "all_of" => schema.insert("allOf".into(), list.into()),
+                "xor" => schema.insert("oneOf".into(), list.into()),
+                _ => schema.insert("not".into(), json!({ "allOf": list })),
+            };
+        }
+        _ => return unsupported(),
+    }
+
+    if rule.param("not") == Some(&Value::Boolean(true)) {
+        let mut not = Object::new();
+        not.insert("not".into(), JsonValue::Object(schema));
+        schema = not;
+    }
+
+    Ok(Fragment::Schema(schema))
+}
+
+fn json_number(value: &Value) -> Option<JsonValue> {
+    let number = match value {
+        Value::Uint8(n) => Number::from(*n),
+        Value::Int8(n) => Number::from(*n),
+        Value::Uint16(n) => Number::from(*n),
+        Value::Int16(n) => Number::from(*n),
+        Value::Uint32(n) => Number::from(*n),
+        Value::Int32(n) => Number::from(*n),
+        Value::Uint64(n) => Number::from(*n),
+        Value::Int64(n) => Number::from(*n),
+        Value::Float32(n) => Number::from_f64(n.get() as f64)?,
+        Value::Float64(n) => Number::from_f64(n.get())?,
+        _ => return None,
+    };
+    Some(JsonValue::Number(number))
+}
+
+/// escape the special characters of regular expression
+fn escape(text: &str) -> String {
+    let mut string = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "^$\\.*+?()[]{}|/".contains(c) {
+            string.push('\\');
+        }
+        string.push(c);
+    }
+    string
+}
+
+/// insert the keywords of other, the conflicting ones are kept in `allOf`
+fn merge(schema: &mut Object, other: Object) {
+    if other.keys().any(|key| schema.contains_key(key)) {
+        match schema.get_mut("allOf") {
+            Some(JsonValue::Array(list)) => list.push(JsonValue::Object(other)),
+            _ => {
+                schema.insert("allOf".into(), json!([other]));
+            }
+        }
+    } else {
+        schema.extend(other);
+    }
+}
+
+fn parse_path(names: &FieldNames) -> Vec<FieldName> {
+    let mut parser = Parser::new(names.as_str());
+    let mut path = Vec::new();
+    // the names are checked when they are registered
+    while let Ok(Some(name)) = parser.next_name() {
+        path.push(name);
+    }
+    path
+}
+
+fn property_key(name: &FieldName) -> Option<&str> {
+    match name {
+        FieldName::Literal(key) | FieldName::StructVariant(key) | FieldName::MapKey(key) => {
+            Some(key)
+        }
+        _ => None,
+    }
+}
+
+fn push_required(schema: &mut Object, key: &str) {
+    let list = schema
+        .entry("required")
+        .or_insert_with(|| JsonValue::Array(Vec::new()));
+    if let JsonValue::Array(list) = list {
+        if !list.iter().any(|item| item == key) {
+            list.push(key.into());
+        }
+    }
+}
+
+fn node_mut<'a>(root: &'a mut Object, path: &[FieldName]) -> &'a mut Object {
+    path.iter().fold(root, child_mut)
+}
+
+/// get the sub-schema of the name, it is created when it is not found
+fn child_mut<'a>(schema: &'a mut Object, name: &FieldName) -> &'a mut Object {
+    let child = match name {
+        FieldName::Literal(key) | FieldName::StructVariant(key) | FieldName::MapKey(key) => {
+            schema.insert("type".into(), "object".into());
+            let properties = object_mut(schema, "properties");
+            properties
+                .entry(key.as_str())
+                .or_insert_with(|| JsonValue::Object(Object::new()))
+        }
+        // the type is unknown, it is completed by `wildcard_map`
+        FieldName::Wildcard => schema
+            .entry("items")
+            .or_insert_with(|| JsonValue::Object(Object::new())),
+        FieldName::Array(_) | FieldName::Tuple(_) => {
+            let index = match name {
+                FieldName::Tuple(n) => *n as usize,
+                FieldName::Array(n) => *n,
+                _ => unreachable!(),
+            };
+            schema.insert("type".into(), "array".into());
+            let items = schema
+                .entry("prefixItems")
+                .or_insert_with(|| JsonValue::Array(Vec::new()));
+            let JsonValue::Array(items) = items else {
+                unreachable!()
+            };
+            if items.len() <= index {
+                items.resize(index + 1, JsonValue::Object(Object::new()));
+            }
+            &mut items[index]
+        }
+    };
+    match child {
+        JsonValue::Object(object) => object,
+        _ => unreachable!(),
+    }
+}
+
+/// `[*]` of the node without type also targets the values of map
+fn wildcard_map(schema: &mut Object) {
+    for key in ["properties", "items", "prefixItems"] {
+        match schema.get_mut(key) {
+            Some(JsonValue::Object(object)) if key == "properties" => object
+                .values_mut()
+                .filter_map(JsonValue::as_object_mut)
+                .for_each(wildcard_map),
+            Some(JsonValue::Object(object)) => wildcard_map(object),
+            Some(JsonValue::Array(list)) => list
+                .iter_mut()
+                .filter_map(JsonValue::as_object_mut)
+                .for_each(wildcard_map),
+            _ => (),
+        }
+    }
+
+    if !schema.contains_key("type") {
+        if let Some(items) = schema.get("items").cloned() {
+            schema.insert("additionalProperties".into(), items);
+        }
+    }
+}
+
+fn object_mut<'a>(schema: &'a mut Object, key: &str) -> &'a mut Object {
+    match schema
+        .entry(key)
+        .or_insert_with(|| JsonValue::Object(Object::new()))
+    {
+        JsonValue::Object(object) => object,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        available::{Confirm, Email, Gt, Length, Message, Not, Range, Regex, Required, StartWith},
+        custom, not, RuleExt, Validator,
+    };
+
+    fn not_foo(_: &mut String) -> Result<(), Message> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested() {
+        let validator = Validator::new()
+            .rule("name", Required.and(StartWith("a.b")))
+            .rule("age", Range::new(18_u8..60).and(Gt(0_u8)))
+            .rule("address.zip", Required.and(Regex::new(r"^\d{6}$")))
+            .rule("tags[*]", Length(..=8).nullable())
+            .rule("point.1", Range::new(0.0..=1.0_f64))
+            .rule("path", StartWith("a-b#c/d"));
+
+        let schema = validator.json_schema();
+        assert!(schema.is_complete());
+        assert_eq!(
+            schema.into_schema(),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "name": { "pattern": "^a\\.b" },
+                    "age": { "minimum": 18, "maximum": 59, "exclusiveMinimum": 0 },
+                    "address": {
+                        "type": "object",
+                        "properties": { "zip": { "pattern": "^\\d{6}$" } },
+                        "required": ["zip"]
+                    },
+                    "tags": {
+                        "items": {
+                            "anyOf": [
+                                { "type": "null" },
+                                { "maxLength": 8, "maxItems": 8 }
+                            ]
+                        },
+                        "additionalProperties": {
+                            "anyOf": [
+                                { "type": "null" },
+                                { "maxLength": 8, "maxItems": 8 }
+                            ]
+                        }
+                    },
+                    "point": {
+                        "type": "array",
+                        "prefixItems": [{}, { "minimum": 0.0, "maximum": 1.0 }]
+                    },
+                    "path": { "pattern": "^a-b#c\\/d" }
+                },
+                "required": ["name"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let validator = Validator::new()
+            .rule("labels[*]", Length(..=8))
+            .rule("matrix[*][*]", Range::new(0_u8..=9))
+            .rule("point[*]", Range::new(0_u8..=9))
+            .rule("point[0]", Required)
+            .rule("profile[*]", Length(..=8))
+            .rule("profile.name", Required);
+
+        let schema = validator.json_schema().into_schema();
+        let properties = &schema["properties"];
+
+        // the field may be an array or a map
+        assert_eq!(
+            properties["labels"],
+            json!({
+                "items": { "maxLength": 8, "maxItems": 8 },
+                "additionalProperties": { "maxLength": 8, "maxItems": 8 }
+            })
+        );
+        let cell = json!({ "minimum": 0, "maximum": 9 });
+        let row = json!({ "items": cell, "additionalProperties": cell });
+        assert_eq!(
+            properties["matrix"],
+            json!({ "items": row, "additionalProperties": row })
+        );
+
+        // the field is known as an array or an object
+        assert_eq!(properties["point"]["type"], json!("array"));
+        assert_eq!(properties["point"].get("additionalProperties"), None);
+        assert_eq!(properties["profile"]["type"], json!("object"));
+        assert_eq!(properties["profile"].get("additionalProperties"), None);
+    }
+
+    #[test]
+    fn test_combinator() {
+        let validator = Validator::new()
+            .rule("contact", Email.or(StartWith('+')).and(Not(Email)))
+            .rule(
+                "name",
+                not(StartWith("admin").and(Length(..6)), Message::fallback("")),
+            )
+            .rule("title", Length(1..).and(Length(2..=9)));
+
+        let schema = validator.json_schema().into_schema();
+        assert_eq!(
+            schema["properties"]["contact"],
+            json!({
+                "anyOf": [{ "format": "email" }, { "pattern": "^\\+" }],
+                "not": { "format": "email" }
+            })
+        );
+        assert_eq!(
+            schema["properties"]["name"],
+            json!({
+                "not": { "allOf": [
+                    { "pattern": "^admin" },
+                    { "maxLength": 5, "maxItems": 5 }
+                ] }
+            })
+        );
+        assert_eq!(
+            schema["properties"]["title"],
+            json!({
+                "minLength": 1,
+                "minItems": 1,
+                "allOf": [{ "minLength": 2, "minItems": 2, "maxLength": 9, "maxItems": 9 }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_non_exportable() {
+        let validator = Validator::new()
+            .rule("name", Required.custom(not_foo).named("foo"))
+            .rule("password", Confirm("password2"))
+            .rule("code", Email.or(Required).and(Range::new('a'..='z')))
+            .rule("tags[*]", Required)
+            .rule("point.0", Required);
+
+        let schema = validator.json_schema();
+        assert!(!schema.is_complete());
+        let list: Vec<_> = schema
+            .non_exportable()
+            .iter()
+            .map(|(field, rule)| (field.as_str(), *rule))
+            .collect();
+        assert_eq!(
+            list,
+            vec![
+                ("name", "foo"),
+                ("password", "confirm"),
+                ("code", "required"),
+                ("code", "range"),
+                ("tags[*]", "required"),
+                ("point.0", "required")
+            ]
+        );
+        assert_eq!(schema.schema()["required"], json!(["name"]));
+        assert_eq!(schema.schema()["properties"]["code"], json!({}));
+
+        let schema = Validator::new().rule("name", custom(not_foo)).json_schema();
+        assert_eq!(schema.non_exportable()[0].1, "custom");
+    }
+
+    #[test]
+    fn test_deny_unknown_fields() {
+        let validator = Validator::new()
+            .rule("address.zip", Required)
+            .rule("profile", Required)
+            .rule("profile.age", Gt(0_u8))
+            .allow_fields(["nickname"])
+            .deny_unknown_fields(Message::fallback("unknown"));
+
+        let schema = validator.json_schema().into_schema();
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert_eq!(schema["properties"]["nickname"], json!({}));
+        assert_eq!(
+            schema["properties"]["address"]["additionalProperties"],
+            json!(false)
+        );
+        assert_eq!(
+            schema["properties"]["profile"].get("additionalProperties"),
+            None
+        );
+    }
+}
@@ -85,7 +85,7 @@
 //!
 //! > custom rule need to implement Clone.
 
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use crate::rule::IntoRuleList;
 
@@ -102,7 +102,7 @@ pub type Validator<M> = InnerValidatorError<String, M>;
 impl<M> Default for Validator<M> {
     fn default() -> Self {
         Self {
-            message: HashMap::new(),
+            message: IndexMap::new(),
         }
     }
 }
@@ -124,9 +124,8 @@ impl<M> Validator<M> {
         F: FnOnce() -> Result<(), M>,
         Field: Into<String>,
     {
-        let res = f();
-        if res.is_err() {
-            self.message.insert(field.into(), vec![res.unwrap_err()]);
+        if let Err(err) = f() {
+            self.message.insert(field.into(), vec![err]);
         }
         self
     }
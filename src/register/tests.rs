@@ -1,4 +1,4 @@
-use super::{FieldNames, Validator, ValidatorError};
+use super::{BuildError, FieldNames, Validator, ValidatorError};
 
 #[test]
 fn test_validator_error_serialize() {
@@ -26,7 +26,7 @@ fn repeat_insert_rules() {
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
     assert_eq!(vec.len(), 2);
-    assert!(vec.is_bail() == false);
+    assert!(!vec.is_bail());
 
     let validate = Validator::new()
         .rule("foo", Required.and(Trim).bail())
@@ -34,7 +34,7 @@ fn repeat_insert_rules() {
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
     assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert!(vec.is_bail());
 
     let validate = Validator::new()
         .rule("foo", Required)
@@ -42,23 +42,23 @@ fn repeat_insert_rules() {
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
     assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert!(vec.is_bail());
 
     let validate = Validator::new()
         .rule("foo", Required.and(Trim).bail())
         .rule("foo", Range::new(1..2).and(Trim).bail());
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
-    assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert_eq!(vec.len(), 4);
+    assert!(vec.is_bail());
 
     let validate = Validator::new()
         .rule("foo", Required.and(Trim).and(Required).bail())
         .rule("foo", Range::new(1..2).and(Trim).and(Required).bail());
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
-    assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert_eq!(vec.len(), 6);
+    assert!(vec.is_bail());
 
     let validate = Validator::new()
         .rule("foo", Required.and(Trim).and(Required).bail())
@@ -67,12 +67,12 @@ fn repeat_insert_rules() {
         .rule("bar", Range::new(1..2).and(Trim).and(Required).bail());
 
     let vec = validate.rules.get(&FieldNames::new("foo".into())).unwrap();
-    assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert_eq!(vec.len(), 6);
+    assert!(vec.is_bail());
 
     let vec = validate.rules.get(&FieldNames::new("bar".into())).unwrap();
-    assert_eq!(vec.len(), 3);
-    assert!(vec.is_bail() == true);
+    assert_eq!(vec.len(), 6);
+    assert!(vec.is_bail());
 }
 
 #[cfg(feature = "full")]
@@ -101,11 +101,13 @@ fn test_total() {
 
 #[cfg(feature = "full")]
 #[test]
-#[should_panic = "field `field3` is not found"]
 fn test_check_field() {
     use serde::{Deserialize, Serialize};
 
-    use crate::{available::Required, Validatable};
+    use crate::{
+        available::{Message, MessageKind, Required, StartWith},
+        RuleExt, Validatable,
+    };
 
     #[derive(Debug, Serialize, Deserialize)]
     struct Foo {
@@ -118,12 +120,34 @@ fn test_check_field() {
         field2: "foo2".into(),
     };
 
-    let _ = value.validate(Validator::new().rule("field3", Required));
+    let err = value
+        .validate(Validator::new().rule("field3", Required.and(StartWith("foo"))))
+        .unwrap_err();
+    assert_eq!(
+        err.get("field3").unwrap(),
+        &vec![Message::new(MessageKind::Missing)]
+    );
+
+    let err = Validator::new()
+        .rule("field3", Required)
+        .message([("field3.required", "field3 is missing")])
+        .validate(&value)
+        .unwrap_err();
+    assert_eq!(
+        err.get("field3").unwrap()[0].to_string(),
+        "field3 is missing"
+    );
+
+    Validator::new()
+        .rule("field3", Required)
+        .rule("field1", StartWith("foo"))
+        .lenient()
+        .validate(&value)
+        .unwrap();
 }
 
 #[cfg(feature = "full")]
 #[test]
-#[should_panic = "field `field3` is not found"]
 fn test_check_field_mut() {
     use serde::{Deserialize, Serialize};
 
@@ -140,5 +164,1397 @@ fn test_check_field_mut() {
         field2: "foo2".into(),
     };
 
-    let _ = value.validate_mut(Validator::new().rule("field3", Required));
+    let err = value
+        .validate_mut(Validator::new().rule("field3", Required))
+        .unwrap_err();
+    assert_eq!(err.len(), 1);
+
+    let value = Foo {
+        field1: "foo1".into(),
+        field2: "foo2".into(),
+    };
+    let value = value
+        .validate_mut(Validator::new().rule("field3", Required).lenient())
+        .unwrap();
+    assert_eq!(value.field1, "foo1");
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn wildcard_array() {
+    use serde::Serialize;
+
+    use crate::available::{Message, MessageKind, Required, StartWith};
+    use crate::RuleExt;
+
+    #[derive(Serialize)]
+    struct Order {
+        items: Vec<LineItem>,
+    }
+
+    #[derive(Serialize)]
+    struct LineItem {
+        name: String,
+    }
+
+    let order = Order {
+        items: vec![
+            LineItem { name: "foo".into() },
+            LineItem { name: "".into() },
+            LineItem { name: "bar".into() },
+            LineItem {
+                name: "foo2".into(),
+            },
+        ],
+    };
+
+    let err = Validator::new()
+        .rule("items[*].name", Required.and(StartWith("foo")))
+        .message([("items[*].name.required", "name is required")])
+        .validate(&order)
+        .unwrap_err();
+
+    assert_eq!(err.len(), 2);
+    assert_eq!(
+        err.get("items[1].name").unwrap(),
+        &vec![
+            Message::from("name is required"),
+            Message::new(MessageKind::StartWith("foo".into()))
+        ]
+    );
+    assert_eq!(
+        err.get("items[2].name").unwrap(),
+        &vec![Message::new(MessageKind::StartWith("foo".into()))]
+    );
+    assert!(err.get("items[0].name").is_none());
+
+    let empty = Order { items: vec![] };
+    Validator::new()
+        .rule("items[*].name", Required)
+        .validate(&empty)
+        .unwrap();
+
+    let err = Validator::new()
+        .rule("items[*].name", Required.and(StartWith("foo")))
+        .bail()
+        .validate(&order)
+        .unwrap_err();
+    assert_eq!(err.total(), 1);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_check_wildcard_field() {
+    use serde::Serialize;
+
+    use crate::available::{Message, MessageKind, Required};
+
+    #[derive(Serialize)]
+    struct Order {
+        items: Vec<LineItem>,
+    }
+
+    #[derive(Serialize)]
+    struct LineItem {
+        name: String,
+    }
+
+    let order = Order {
+        items: vec![LineItem { name: "foo".into() }],
+    };
+
+    let err = Validator::new()
+        .rule("items[*].title", Required)
+        .validate(&order)
+        .unwrap_err();
+    assert_eq!(
+        err.get("items[0].title").unwrap(),
+        &vec![Message::new(MessageKind::Missing)]
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn map_key() {
+    use std::collections::HashMap;
+
+    use serde::Serialize;
+
+    use crate::available::{Length, Required};
+
+    #[derive(Serialize)]
+    struct Input {
+        labels: HashMap<String, String>,
+    }
+
+    let mut labels = HashMap::new();
+    labels.insert("region".to_string(), "".to_string());
+    labels.insert("app.name".to_string(), "foo_bar_baz".to_string());
+    let input = Input { labels };
+
+    let err = Validator::new()
+        .rule("labels.region", Required)
+        .rule(r#"labels["app.name"]"#, Length(1..10))
+        .message([(r#"labels["app.name"].length"#, "too long")])
+        .validate(&input)
+        .unwrap_err();
+
+    assert_eq!(err.len(), 2);
+    assert!(err.contains_key("labels.region"));
+    assert_eq!(err[r#"labels["app.name"]"#][0].to_string(), "too long");
+
+    let err = Validator::new()
+        .rule("labels[*]", Required)
+        .validate(&input)
+        .unwrap_err();
+
+    assert_eq!(err.len(), 1);
+    assert!(err.contains_key(r#"labels["region"]"#));
+
+    // the key is not addressable, it is not skipped in lenient mode
+    let mut labels = HashMap::new();
+    labels.insert(r#"a"b"#.to_string(), "foo".to_string());
+    let err = Validator::new()
+        .rule("labels[*]", Required)
+        .lenient()
+        .validate(&Input { labels })
+        .unwrap_err();
+
+    assert_eq!(
+        err["labels[*]"][0].kind(),
+        &crate::available::MessageKind::Missing
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn shared_validator() {
+    use std::sync::{Arc, OnceLock};
+
+    use serde::Serialize;
+
+    use crate::{
+        available::{Message, Required, Trim},
+        custom, RuleExt, Validatable,
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Validator<'static, Message>>();
+    assert_send_sync::<crate::ValidPhrase<'static>>();
+
+    #[derive(Serialize)]
+    struct Input {
+        name: &'static str,
+        age: u8,
+    }
+
+    fn age_limit(age: &mut u8) -> Result<(), Message> {
+        if *age >= 18 {
+            Ok(())
+        } else {
+            Err("age should be greater than 18".into())
+        }
+    }
+
+    static VALIDATOR: OnceLock<Validator<'static, Message>> = OnceLock::new();
+
+    let validator = VALIDATOR.get_or_init(|| {
+        Validator::new()
+            .rule("name", Trim.and(Required))
+            .rule("age", custom(age_limit))
+    });
+
+    Input {
+        name: "foo",
+        age: 20,
+    }
+    .validate(validator)
+    .unwrap();
+    let err = validator
+        .validate(Input { name: " ", age: 10 })
+        .unwrap_err();
+    assert_eq!(err.len(), 2);
+    validator
+        .validate(Input {
+            name: "bar",
+            age: 30,
+        })
+        .unwrap();
+
+    let validator = Arc::new(validator.clone().bail());
+    let handles: Vec<_> = (0..4_u8)
+        .map(|i| {
+            let validator = Arc::clone(&validator);
+            std::thread::spawn(move || {
+                validator
+                    .validate(Input {
+                        name: "",
+                        age: 15 + i,
+                    })
+                    .unwrap_err()
+                    .total()
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 1);
+    }
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn registration_order() {
+    use serde::Serialize;
+
+    use crate::{
+        available::{Length, Message, Required, StartWith},
+        custom,
+        register::string,
+        RuleExt, ValidPhrase,
+    };
+
+    #[derive(Serialize)]
+    struct Input {
+        a: &'static str,
+        b: &'static str,
+        c: &'static str,
+        d: &'static str,
+        e: &'static str,
+        f: &'static str,
+    }
+
+    let input = Input {
+        a: "",
+        b: "",
+        c: "",
+        d: "",
+        e: "",
+        f: "",
+    };
+    let fields = ["f", "c", "e", "a", "d", "b"];
+
+    for _ in 0..5 {
+        let err = Validator::new()
+            .rule("f", StartWith("x").and(Required))
+            .rule("c", Required)
+            .rule("e", Required)
+            .rule("a", Required)
+            .rule("d", Required)
+            .rule("b", Required)
+            .rule("f", Length(1..2))
+            .validate(&input)
+            .unwrap_err();
+
+        let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+        assert_eq!(keys, fields);
+        assert_eq!(
+            err["f"],
+            vec![
+                Message::new(crate::available::MessageKind::StartWith("x".into())),
+                Message::new(crate::available::MessageKind::Required),
+                Message::new(crate::available::MessageKind::Length),
+            ]
+        );
+
+        let json = serde_json::to_string(&err).unwrap();
+        assert!(json.starts_with(r#"{"f":"#));
+        assert!(json.ends_with(r#""b":[{"kind":"required"}]}"#));
+
+        fn required(v: &mut crate::Value) -> Result<(), String> {
+            if matches!(v, crate::Value::String(s) if s.is_empty()) {
+                Err("required".into())
+            } else {
+                Ok(())
+            }
+        }
+        let err = ValidPhrase::new()
+            .rule("f", custom(required))
+            .rule("c", custom(required))
+            .rule("e", custom(required))
+            .rule("a", custom(required))
+            .rule("d", custom(required))
+            .rule("b", custom(required))
+            .validate(&input)
+            .unwrap_err();
+        let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+        assert_eq!(keys, fields);
+
+        let mut value = String::new();
+        let err = fields
+            .iter()
+            .fold(string::Validator::<Message>::new(), |valid, field| {
+                valid.insert(*field, &mut value, Required)
+            });
+        let keys: Vec<_> = err.into_iter().map(|(field, _)| field).collect();
+        assert_eq!(keys, fields);
+    }
+}
+
+#[test]
+fn validate_with_context() {
+    use crate::{custom, custom_with_context, ValueMap};
+
+    struct Tenant {
+        max_seats: u8,
+    }
+
+    fn seats_limit(seats: &mut u8, tenant: &Tenant) -> Result<(), String> {
+        if *seats <= tenant.max_seats {
+            Ok(())
+        } else {
+            Err("seats limit".into())
+        }
+    }
+    fn has_tenant(data: &mut ValueMap) -> Result<(), String> {
+        match data.context::<Tenant>() {
+            Some(_) => Ok(()),
+            None => Err("no tenant".into()),
+        }
+    }
+
+    let validator = Validator::new()
+        .rule("0", custom_with_context(seats_limit).custom(has_tenant))
+        .rule("1", custom(has_tenant));
+
+    validator
+        .validate_with_context((5_u8, 1_u8), Tenant { max_seats: 10 })
+        .unwrap();
+
+    let err = validator
+        .validate_with_context((15_u8, 1_u8), Tenant { max_seats: 10 })
+        .unwrap_err();
+    assert_eq!(err["0"], vec!["seats limit".to_string()]);
+
+    let (seats, _) = validator
+        .validate_mut_with_context((5_u8, 1_u8), Tenant { max_seats: 5 })
+        .unwrap();
+    assert_eq!(seats, 5);
+
+    // other context type is not matched
+    let err = Validator::new()
+        .rule("1", custom(has_tenant))
+        .validate_with_context((5_u8, 1_u8), 10_u8)
+        .unwrap_err();
+    assert_eq!(err["1"], vec!["no tenant".to_string()]);
+
+    // the closure of context fails without context, or with other context type
+    let err = validator.validate((5_u8, 1_u8)).unwrap_err();
+    assert_eq!(err["0"].len(), 2);
+    assert!(err["0"][0].contains("Tenant"));
+
+    let err = validator
+        .validate_with_context((5_u8, 1_u8), 10_u8)
+        .unwrap_err();
+    assert!(err["0"][0].contains("Tenant"));
+}
+
+#[test]
+fn message_not_sync() {
+    use std::cell::Cell;
+
+    use crate::custom;
+
+    // it is converted into `String` when it is inserted, so it is not required to be `Sync`
+    struct Text(Cell<&'static str>);
+
+    impl From<Text> for String {
+        fn from(text: Text) -> Self {
+            text.0.get().to_string()
+        }
+    }
+
+    fn fail(_: &mut u8) -> Result<(), String> {
+        Err("default".into())
+    }
+
+    let err = Validator::new()
+        .rule("0", custom(fail))
+        .message([("0.custom", Text(Cell::new("custom message")))])
+        .validate((1_u8,))
+        .unwrap_err();
+    assert_eq!(err["0"], vec!["custom message".to_string()]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn closure_missing_and_mismatch() {
+    use std::collections::HashMap;
+
+    use crate::{
+        available::{Message, MessageKind},
+        checked, custom,
+    };
+
+    fn is_adult(age: &mut u8) -> Result<(), Message> {
+        if *age >= 18 {
+            Ok(())
+        } else {
+            Err(Message::fallback("too young"))
+        }
+    }
+
+    // a plain closure is skipped when the field is not found
+    Validator::new()
+        .rule("age", custom(is_adult))
+        .validate(HashMap::from([("name", 1_u8)]))
+        .unwrap();
+
+    let validator = Validator::new().rule("age", custom(checked(is_adult)));
+
+    let err = validator
+        .validate(HashMap::from([("name", 1_u8)]))
+        .unwrap_err();
+    assert_eq!(err["age"], vec![Message::new(MessageKind::Missing)]);
+
+    let err = validator
+        .validate(HashMap::from([("age", "ten")]))
+        .unwrap_err();
+    assert_eq!(
+        err["age"],
+        vec![Message::new(MessageKind::Mismatch(
+            std::any::type_name::<u8>().to_string()
+        ))]
+    );
+
+    Validator::new()
+        .rule("age", custom(checked(is_adult)))
+        .lenient()
+        .validate(HashMap::from([("name", 1_u8)]))
+        .unwrap();
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn validate_groups() {
+    use crate::{
+        available::{Length, Message, MessageKind, Required, StartWith},
+        RuleExt,
+    };
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+        password: String,
+    }
+    let user = User {
+        id: 0,
+        name: "".into(),
+        password: "".into(),
+    };
+
+    let validator = Validator::new()
+        .rule("name", Required)
+        .message([("name.required", "name is required")])
+        .group("create", |v| {
+            v.rule("password", Required.and(Length(6..=20)))
+                .rule("name", StartWith("u_"))
+                .message([("password.required", "password is required")])
+                .bail()
+        })
+        .group("update", |v| v.rule("id", Required))
+        .group("create", |v| v.rule("id", Required));
+
+    // rules out of groups
+    let err = validator.validate(&user).unwrap_err();
+    assert_eq!(err.len(), 1);
+
+    let err = validator.validate_groups(&user, ["update"]).unwrap_err();
+    assert_eq!(
+        err.keys().map(FieldNames::as_str).collect::<Vec<_>>(),
+        ["name"]
+    );
+
+    // group bail stops at the first error of the group
+    let err = validator.validate_groups(&user, ["create"]).unwrap_err();
+    assert_eq!(err.total(), 2);
+    assert_eq!(err["name"], vec![Message::from("name is required")]);
+    assert_eq!(err["password"], vec![Message::from("password is required")]);
+
+    // fields of groups are merged
+    let err = Validator::new()
+        .rule("name", Required)
+        .group("create", |v| v.rule("name", StartWith("u_")))
+        .validate_groups(&user, ["create"])
+        .unwrap_err();
+    assert_eq!(
+        err["name"],
+        vec![
+            Message::new(MessageKind::Required),
+            Message::new(MessageKind::StartWith("u_".into()))
+        ]
+    );
+
+    let user = validator
+        .validate_mut_groups(
+            User {
+                id: 1,
+                name: "u_foo".into(),
+                password: "123456".into(),
+            },
+            ["create"],
+        )
+        .unwrap();
+    assert_eq!(user.id, 1);
+}
+
+#[cfg(feature = "full")]
+#[test]
+#[should_panic = "the group \"delete\" not found in validator"]
+fn validate_unknown_group() {
+    use crate::available::Required;
+
+    let _ = Validator::new()
+        .group("create", |v| v.rule("0", Required))
+        .validate_groups((1_u8,), ["delete"]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn validate_refine_groups() {
+    use crate::{
+        available::{Required, StartWith},
+        custom, Value,
+    };
+
+    use super::{IntoMessage, ValidatorRefine};
+
+    #[derive(Debug, PartialEq)]
+    struct RuleName(&'static str);
+
+    impl IntoMessage for RuleName {
+        fn into_message(rule: &'static str, _field: &FieldNames, _value: &Value) -> Self {
+            RuleName(rule)
+        }
+    }
+
+    let validator: ValidatorRefine<_> = Validator::new()
+        .rule("0", StartWith("u_"))
+        .group("create", |v| v.rule("1", Required))
+        .into();
+
+    let err = validator
+        .validate_groups::<_, _, RuleName>(("u_foo", ""), ["create"])
+        .unwrap_err();
+    assert_eq!(err["1"], vec![RuleName("required")]);
+
+    #[allow(clippy::ptr_arg)]
+    fn required(name: &mut String) -> Result<(), String> {
+        if name.is_empty() {
+            Err("required".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    let err = crate::ValidPhrase::new()
+        .rule("0", custom(required))
+        .group("create", |v| {
+            v.rule("1", custom(required))
+                .message([("1.custom", "{field} is required in create")])
+        })
+        .validate_groups(("u_foo", ""), ["create"])
+        .unwrap_err();
+    assert_eq!(err["1"], vec!["1 is required in create".to_string()]);
+}
+
+#[test]
+#[should_panic = "field `address.` is invalid"]
+fn nest_invalid_prefix() {
+    let _ = Validator::<String>::new().nest("address.", Validator::new());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn nest_validator() {
+    use crate::{
+        available::{Message, Range, Required, StartWith},
+        RuleExt,
+    };
+
+    #[derive(serde::Serialize)]
+    struct Money {
+        amount: u32,
+        currency: &'static str,
+    }
+    #[derive(serde::Serialize)]
+    struct Address {
+        city: &'static str,
+    }
+    #[derive(serde::Serialize)]
+    struct Order {
+        shipping_address: Address,
+        billing_address: Address,
+        items: Vec<Money>,
+        total: Money,
+    }
+
+    let address = Validator::new()
+        .rule("city", Required)
+        .message([("city.required", "city is required")])
+        .group("create", |v| v.rule("city", StartWith("N")));
+    let money = Validator::new()
+        .rule("amount", Range::new(1_u32..100))
+        .rule("currency", Required.and(StartWith("U")).bail());
+
+    let validator = Validator::new()
+        .nest("shipping_address", address.clone())
+        .nest("billing_address", address)
+        .nest("items[*]", money.clone())
+        .nest("total", money)
+        .rule("total.amount", Required);
+
+    let order = Order {
+        shipping_address: Address { city: "" },
+        billing_address: Address { city: "Paris" },
+        items: vec![
+            Money {
+                amount: 10,
+                currency: "USD",
+            },
+            Money {
+                amount: 0,
+                currency: "",
+            },
+        ],
+        total: Money {
+            amount: 200,
+            currency: "EUR",
+        },
+    };
+
+    let err = validator.validate(&order).unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(
+        keys,
+        [
+            "shipping_address.city",
+            "items[1].amount",
+            "items[1].currency",
+            "total.amount",
+            "total.currency"
+        ]
+    );
+    assert_eq!(
+        err["shipping_address.city"],
+        vec![Message::from("city is required")]
+    );
+    assert_eq!(err["items[1].currency"].len(), 1);
+    let list = validator.rule_get(&"total.amount".into()).unwrap();
+    assert_eq!(list.len(), 2);
+
+    let err = validator.validate_groups(&order, ["create"]).unwrap_err();
+    assert_eq!(err["billing_address.city"].len(), 1);
+    assert_eq!(err["shipping_address.city"].len(), 2);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn each_item() {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        available::{Message, MessageKind, Required, StartWith},
+        RuleExt,
+    };
+
+    #[derive(serde::Serialize)]
+    struct Post {
+        tags: Vec<&'static str>,
+        pair: (&'static str, &'static str),
+        labels: BTreeMap<&'static str, &'static str>,
+    }
+
+    let post = Post {
+        tags: vec!["#rust", "", "go"],
+        pair: ("#a", "b"),
+        labels: BTreeMap::from([("env", "#prod"), ("team", "")]),
+    };
+
+    let validator = Validator::new()
+        .each("tags", Required.and(StartWith("#")))
+        .rule("pair", Required.and(StartWith("#")).each())
+        .each("labels", Required);
+
+    let err = validator.validate(&post).unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(keys, ["tags[1]", "tags[2]", "pair.1", r#"labels["team"]"#]);
+    assert_eq!(
+        err["tags[1]"],
+        vec![
+            Message::new(MessageKind::Required),
+            Message::new(MessageKind::StartWith("#".into()))
+        ]
+    );
+
+    // the keys of map are not string
+    let err = Validator::new()
+        .each("0", StartWith("#"))
+        .validate((BTreeMap::from([(1_u32, "#a"), (2_u32, "b")]),))
+        .unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(keys, [r#"0["2"]"#]);
+
+    // same as `[*]`
+    let validator = Validator::new()
+        .each("tags", Required)
+        .rule("tags[*]", StartWith("#"));
+    assert_eq!(validator.rules.len(), 1);
+    assert_eq!(validator.rule_get(&"tags[*]".into()).unwrap().len(), 2);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn option_path_and_nullable() {
+    use crate::{
+        available::{Message, MessageKind, Required, StartWith},
+        RuleExt,
+    };
+
+    #[derive(serde::Serialize)]
+    struct Avatar {
+        url: &'static str,
+    }
+    #[derive(serde::Serialize)]
+    struct Profile {
+        avatar: Option<Avatar>,
+        nickname: Option<&'static str>,
+        tags: Option<Vec<&'static str>>,
+    }
+    #[derive(serde::Serialize)]
+    struct User {
+        profile: Profile,
+    }
+
+    let validator = Validator::new()
+        .rule("profile.avatar.url", StartWith("https://").nullable())
+        .rule("profile.nickname", Required.and(StartWith("n_")).nullable())
+        .rule("profile.tags[*]", Required);
+
+    let user = User {
+        profile: Profile {
+            avatar: Some(Avatar { url: "http://a" }),
+            nickname: Some("foo"),
+            tags: Some(vec!["a", ""]),
+        },
+    };
+    let err = validator.validate(&user).unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(
+        keys,
+        ["profile.avatar.url", "profile.nickname", "profile.tags[1]"]
+    );
+
+    // the rules of absent option are skipped
+    let user = User {
+        profile: Profile {
+            avatar: None,
+            nickname: None,
+            tags: None,
+        },
+    };
+    validator.validate(&user).unwrap();
+
+    // `Required` fails on `None`
+    let err = Validator::new()
+        .rule("profile.nickname", Required)
+        .validate(&user)
+        .unwrap_err();
+    assert_eq!(
+        err["profile.nickname"],
+        vec![Message::new(MessageKind::Required)]
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn deny_unknown_fields() {
+    use std::collections::HashMap;
+
+    use crate::available::{Message, MessageKind, Required, StartWith};
+
+    #[derive(serde::Serialize)]
+    struct Address {
+        city: &'static str,
+        zip: &'static str,
+    }
+    #[derive(serde::Serialize)]
+    struct User {
+        name: &'static str,
+        nickname: &'static str,
+        address: Address,
+        meta: HashMap<&'static str, u8>,
+    }
+
+    let user = User {
+        name: "foo",
+        nickname: "bar",
+        address: Address {
+            city: "Paris",
+            zip: "75001",
+        },
+        meta: HashMap::from([("age", 18)]),
+    };
+
+    let address = Validator::new()
+        .rule("city", StartWith("P"))
+        .allow_fields(["zip"]);
+    let validator = Validator::new()
+        .rule("name", Required)
+        .nest("address", address)
+        .group("profile", |v| v.rule("nickname", Required))
+        .deny_unknown_fields(MessageKind::UnknownField);
+
+    let err = validator.validate(&user).unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(keys, ["meta"]);
+    assert_eq!(err["meta"], vec![Message::new(MessageKind::UnknownField)]);
+
+    validator
+        .clone()
+        .allow_fields(["meta"])
+        .validate(&user)
+        .unwrap();
+
+    // every unknown key of the map is reported by the full path
+    let err = validator
+        .clone()
+        .rule("meta.name", Required)
+        .lenient()
+        .validate(&user)
+        .unwrap_err();
+    let keys: Vec<_> = err.keys().map(FieldNames::as_str).collect();
+    assert_eq!(keys, ["meta[\"age\"]"]);
+
+    // unknown fields are not checked without `deny_unknown_fields`
+    Validator::new()
+        .rule("name", Required)
+        .validate(&user)
+        .unwrap();
+}
+
+#[test]
+#[should_panic = "field `meta.` is invalid"]
+fn allow_invalid_fields() {
+    let _ = Validator::<String>::new().allow_fields(["meta."]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn try_build() {
+    use crate::{
+        available::{Required, StartWith},
+        Rule, RuleExt, Value,
+    };
+
+    #[derive(Clone)]
+    struct BadName;
+
+    impl Rule for BadName {
+        type Message = crate::available::Message;
+
+        const NAME: &'static str = "1bad";
+
+        fn message(&self) -> Self::Message {
+            "bad".into()
+        }
+
+        fn call(&mut self, _data: &mut Value) -> bool {
+            true
+        }
+    }
+
+    let validator = Validator::new()
+        .try_rule("name", Required.and(StartWith("foo")))
+        .unwrap()
+        .try_rule("items[*].title", Required)
+        .unwrap();
+
+    let err = validator
+        .clone()
+        .try_rule("items[", Required)
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::Field { field, .. } if field == "items["));
+
+    let err = validator.clone().try_rule("name", BadName).err().unwrap();
+    assert_eq!(
+        err,
+        BuildError::RuleName {
+            field: "name".into(),
+            rule: "1bad".into()
+        }
+    );
+
+    let err = validator
+        .clone()
+        .try_message([("name_required", "name is required")])
+        .err()
+        .unwrap();
+    assert_eq!(err, BuildError::MessageKey("name_required".into()));
+
+    let err = validator
+        .clone()
+        .try_message([("age.required", "age is required")])
+        .err()
+        .unwrap();
+    assert_eq!(err, BuildError::UnknownField("age".into()));
+    assert_eq!(err.to_string(), "the field \"age\" not found in validator");
+
+    let validator = validator
+        .try_message([
+            ("name.required", "name is required"),
+            ("items[*].title.required", "title is required"),
+        ])
+        .unwrap();
+    assert_eq!(validator.get_message().len(), 2);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn rule_instances() {
+    use crate::{
+        available::{Message, MessageKind, StartWith},
+        RuleExt,
+    };
+
+    let validator = Validator::new()
+        .rule("name", StartWith("a").and(StartWith("b")))
+        .rule(
+            "title",
+            StartWith("a")
+                .and(StartWith("b"))
+                .and(StartWith("c"))
+                .bail(),
+        );
+    assert_eq!(validator.rules[0].len(), 2);
+    assert_eq!(validator.rules[1].len(), 3);
+
+    #[derive(serde::Serialize)]
+    struct Post {
+        name: &'static str,
+        title: &'static str,
+    }
+    let post = Post {
+        name: "ab",
+        title: "c",
+    };
+
+    let err = validator
+        .clone()
+        .message([
+            ("name.start_with#1", "name should start with b"),
+            ("title.start_with", "title is invalid"),
+        ])
+        .validate(&post)
+        .unwrap_err();
+    assert_eq!(err["name"], vec![Message::from("name should start with b")]);
+    assert_eq!(err["title"], vec![Message::from("title is invalid")]);
+
+    // the message of default is kept without the custom message
+    let err = validator.validate(&post).unwrap_err();
+    assert_eq!(
+        err["name"],
+        vec![Message::new(MessageKind::StartWith("b".into()))]
+    );
+
+    // the instance is out of range
+    let err = Validator::new()
+        .rule("name", StartWith("a").and(StartWith("b")))
+        .try_message([("name.start_with#2", "foo")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BuildError::UnknownRule {
+            field: "name".into(),
+            rule: "start_with#2".into()
+        }
+    );
+
+    // inner rules of combinators are the first instance
+    let validator = Validator::new()
+        .rule("name", StartWith("a"))
+        .rule("name", crate::any_of(StartWith("b").and(StartWith("c"))))
+        .try_message([("name.start_with#0", "foo")])
+        .unwrap();
+    let err = validator
+        .try_message([("name.start_with#1", "foo")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BuildError::UnknownRule {
+            field: "name".into(),
+            rule: "start_with#1".into()
+        }
+    );
+
+    // registering the field again keeps the rules with the same name as other instances
+    let validator = Validator::new()
+        .rule("name", StartWith("a").and(StartWith("b")))
+        .rule("name", StartWith("c"));
+    assert_eq!(validator.rules[0].len(), 3);
+
+    let err = validator
+        .message([("name.start_with#2", "name should start with c")])
+        .validate(&post)
+        .unwrap_err();
+    assert_eq!(
+        err["name"],
+        vec![
+            Message::new(MessageKind::StartWith("b".into())),
+            Message::from("name should start with c")
+        ]
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn string_same_name_rules() {
+    use crate::{
+        available::{Message, MessageKind, StartWith},
+        rule::string::StringRuleExt,
+    };
+
+    let mut value = String::from("ab");
+    let err = crate::register::string::Validator::new()
+        .insert("name", &mut value, StartWith("a").and(StartWith("c")))
+        .validate(())
+        .unwrap_err();
+    let messages: Vec<_> = err.into_iter().flat_map(|(_, msg)| msg).collect();
+    assert_eq!(
+        messages,
+        vec![Message::new(MessageKind::StartWith("c".into()))]
+    );
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn named_custom() {
+    use crate::{
+        available::{Message, Required},
+        custom, named, RuleExt, Value,
+    };
+
+    use super::{IntoMessage, ValidatorRefine};
+
+    fn min_age(age: &mut u8) -> Result<(), Message> {
+        if *age >= 18 {
+            Ok(())
+        } else {
+            Err("too young".into())
+        }
+    }
+    fn max_age(age: &mut u8) -> Result<(), Message> {
+        if *age <= 60 {
+            Ok(())
+        } else {
+            Err("too old".into())
+        }
+    }
+
+    let validator = Validator::new()
+        .rule(
+            "0",
+            Required
+                .custom(min_age)
+                .named("min_age")
+                .custom(max_age)
+                .named("max_age"),
+        )
+        .rule("1", custom(min_age).custom(max_age))
+        .rule("2", named("min_age", min_age))
+        .message([
+            ("0.min_age", "age should be 18 at least"),
+            ("0.max_age", "age should be 60 at most"),
+            ("1.custom#1", "the second closure"),
+        ]);
+
+    let err = validator.validate((10_u8, 70_u8, 10_u8)).unwrap_err();
+    assert_eq!(err["0"], vec![Message::from("age should be 18 at least")]);
+    assert_eq!(err["2"], vec![Message::from("too young")]);
+    assert_eq!(err["1"], vec![Message::from("the second closure")]);
+
+    let err = validator.validate((70_u8, 10_u8, 18_u8)).unwrap_err();
+    assert_eq!(err["0"], vec![Message::from("age should be 60 at most")]);
+    assert_eq!(err["1"], vec![Message::from("too young")]);
+
+    #[derive(Debug, PartialEq)]
+    struct RuleName(&'static str);
+
+    impl IntoMessage for RuleName {
+        fn into_message(rule: &'static str, _field: &FieldNames, _value: &Value) -> Self {
+            RuleName(rule)
+        }
+    }
+
+    let validator: ValidatorRefine<Message> = validator.into();
+    let err = validator
+        .validate::<_, RuleName>((10_u8, 18_u8))
+        .unwrap_err();
+    assert_eq!(err["0"], vec![RuleName("min_age")]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+#[should_panic = "the built-in rule `trim` can not be named `name_trim`"]
+fn named_built_in() {
+    use crate::{
+        available::{Required, Trim},
+        RuleExt,
+    };
+
+    let _ = Validator::new().rule("name", Required.and(Trim).named("name_trim"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn inline_message() {
+    use crate::{
+        available::{Email, Message, MessageKind, Required},
+        custom, RuleExt,
+    };
+
+    fn not_foo(name: &mut String) -> Result<(), Message> {
+        if name == "foo" {
+            Err("the name is foo".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    let validator = Validator::new()
+        .rule("0", Required.with_message("title is required"))
+        .rule("1", Required.and(Email).with_message("invalid email"))
+        .rule("2", custom(not_foo).with_message("foo is reserved"))
+        .rule("3", Required.and(Email).with_message("invalid email"))
+        .message([("3.email", "the email is invalid")]);
+
+    let err = validator
+        .clone()
+        .validate(("", "", "foo", "bar"))
+        .unwrap_err();
+    assert_eq!(err["0"], vec![Message::from("title is required")]);
+    assert_eq!(
+        err["1"],
+        vec![
+            Message::from("invalid email"),
+            Message::from("invalid email")
+        ]
+    );
+    assert_eq!(err["2"], vec![Message::from("foo is reserved")]);
+    assert_eq!(err["3"], vec![Message::from("the email is invalid")]);
+
+    let err = validator.validate(("a", "b", "c", "d")).unwrap_err();
+    assert_eq!(err["1"], vec![Message::from("invalid email")]);
+
+    // the rules appended later keep their own messages
+    let err = Validator::new()
+        .rule("0", Required.with_message("title is required").and(Email))
+        .rule("1", Required.with_message("email is required"))
+        .rule("1", Email.with_message("invalid email"))
+        .validate(("", "a"))
+        .unwrap_err();
+    assert_eq!(
+        err["0"],
+        vec![
+            Message::from("title is required"),
+            Message::new(MessageKind::Email)
+        ]
+    );
+    assert_eq!(err["1"], vec![Message::from("invalid email")]);
+
+    let validator = Validator::new()
+        .rule("0", Required.with_message("title is required"))
+        .map(|msg: Message| msg.to_string());
+    let err = validator.validate(("",)).unwrap_err();
+    assert_eq!(err["0"], vec!["title is required".to_string()]);
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn introspection() {
+    use crate::{
+        available::{Confirm, Email, Length, Message, Not, Range, Required, StartWith},
+        custom, RuleExt, Value,
+    };
+
+    fn not_foo(_: &mut String) -> Result<(), Message> {
+        Ok(())
+    }
+
+    let validator = Validator::new()
+        .rule("name", Required.and(Length(..6)).bail())
+        .rule("age", Range::new(0.0..1.0_f32).nullable())
+        .rule(
+            "contact",
+            Email.or(StartWith('+')).and(Not(StartWith("admin"))),
+        )
+        .rule("confirm", Confirm("password").custom(not_foo).named("foo"))
+        .rule("other", custom(not_foo).with_message("not foo"))
+        .bail();
+
+    assert!(validator.is_bail());
+    assert!(!validator.is_lenient());
+
+    let fields = validator.fields();
+    assert_eq!(fields.len(), 5);
+
+    assert_eq!(fields[0].names(), &FieldNames::new("name".into()));
+    assert!(fields[0].is_bail());
+    assert!(!fields[0].is_nullable());
+    let length = &fields[0].rules()[1];
+    assert_eq!(length.name(), "length");
+    assert_eq!(length.params(), &[("max", Value::Uint64(5))]);
+
+    assert!(fields[1].is_nullable());
+    let range = &fields[1].rules()[0];
+    assert_eq!(
+        range.params(),
+        &[
+            ("min", Value::Float32(0.0.into())),
+            ("exclusive_max", Value::Float32(1.0.into()))
+        ]
+    );
+
+    let any_of = &fields[2].rules()[0];
+    assert_eq!(any_of.name(), "any_of");
+    assert_eq!(any_of.inner().len(), 2);
+    assert_eq!(any_of.inner()[0].name(), "email");
+    assert_eq!(
+        any_of.inner()[1].param("prefix"),
+        Some(&Value::String("+".into()))
+    );
+    let not = &fields[2].rules()[1];
+    assert_eq!(not.name(), "start_with");
+    assert_eq!(not.param("not"), Some(&Value::Boolean(true)));
+
+    let rules = fields[3].rules();
+    assert_eq!(
+        rules[0].param("other"),
+        Some(&Value::String("password".into()))
+    );
+    assert!(!rules[0].is_custom());
+    assert_eq!(rules[1].name(), "foo");
+    assert!(rules[1].is_custom());
+
+    assert!(fields[4].rules()[0].is_custom());
+
+    let debug = format!("{validator:?}");
+    assert!(debug.starts_with("Validator {"));
+    assert!(debug.contains("any_of"));
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn declarative_macros() {
+    use crate::{
+        available::{Length, Message, Required, StartWith},
+        messages, validator, RuleExt,
+    };
+
+    #[derive(serde::Serialize)]
+    struct Address {
+        city: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Input {
+        name: &'static str,
+        address: Address,
+        tags: Vec<&'static str>,
+    }
+
+    let validator = validator! {
+        name: Required.and(Length(1..=20)),
+        address.city: Required,
+        "tags[*]": StartWith("#");
+        messages {
+            name.required => "name is required",
+            address.city.required => "city is required",
+            "tags[*].start_with" => "tag should start with #",
+        }
+    };
+
+    let err = validator
+        .validate(Input {
+            name: "",
+            address: Address { city: "" },
+            tags: vec!["#rust", "go"],
+        })
+        .unwrap_err();
+    assert_eq!(err["name"][0], Message::from("name is required"));
+    assert_eq!(err["address.city"], vec![Message::from("city is required")]);
+    assert_eq!(
+        err["tags[1]"],
+        vec![Message::from("tag should start with #")]
+    );
+
+    let validator: Validator<Message> = validator! { name: Required, };
+    assert_eq!(validator.fields().len(), 1);
+
+    let list = messages! { name.required => "a", "name.start_with#1" => "b" };
+    assert_eq!(list, [("name.required", "a"), ("name.start_with#1", "b")]);
+
+    for key in [
+        "name",
+        "name.",
+        ".required",
+        "name..required",
+        "[0.required",
+    ] {
+        let result = std::panic::catch_unwind(|| crate::register::__message_key(key));
+        assert!(result.is_err(), "{key} should be invalid");
+    }
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn validator_label() {
+    use crate::{
+        available::{Message, Required},
+        custom,
+    };
+
+    fn too_short(_: &mut String) -> Result<(), String> {
+        Err("{label} is too short".into())
+    }
+
+    let validator = Validator::new()
+        .rule("name", Required)
+        .rule("title", Required)
+        .label("name", "Full name")
+        .message([
+            ("name.required", "{label} is required"),
+            ("title.required", "{label} is required"),
+        ]);
+    let err = validator
+        .validate(std::collections::HashMap::from([
+            ("name", ""),
+            ("title", ""),
+        ]))
+        .unwrap_err();
+    assert_eq!(err["name"], vec![Message::from("Full name is required")]);
+    assert_eq!(err["title"], vec![Message::from("title is required")]);
+
+    let err = Validator::new()
+        .rule("0", custom(too_short))
+        .label("0", "Nickname")
+        .validate(("ab",))
+        .unwrap_err();
+    assert_eq!(err["0"], vec!["Nickname is too short".to_string()]);
+}
+
+#[test]
+#[should_panic = "the field \"name\" not found in validator"]
+fn label_unknown_field() {
+    let _ = Validator::<String>::new().label("name", "Full name");
+}
+
+#[test]
+#[should_panic = "field `name.` is invalid"]
+fn label_invalid_field() {
+    let _ = Validator::<String>::new().label("name.", "Full name");
 }
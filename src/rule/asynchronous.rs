@@ -0,0 +1,538 @@
+//! define AsyncRule trait, it is used by I/O-backed checks, e.g. "username is not taken"
+//!
+//! async rules and sync rules are able to be mixed in one [`RuleList`], they are executed
+//! in declared order by [`Validator::validate_async`], and `bail` is respected.
+//!
+//! *async rules only be called by [`Validator::validate_async`] and [`Validator::validate_mut_async`],
+//! calling them by sync `validate` panics, because the result of them is unknown.*
+//!
+//! # Example
+//! ```rust
+//! # #[cfg(feature = "full")]
+//! # {
+//! # use std::{collections::HashSet, sync::Arc, time::Duration};
+//! # use valitron::{available::{Required, Message}, AsyncRule, AsyncRuleExt, Validator, Value};
+//! #[derive(Clone)]
+//! struct Unique(Arc<HashSet<String>>);
+//!
+//! impl AsyncRule for Unique {
+//!     type Message = Message;
+//!
+//!     const NAME: &'static str = "unique";
+//!
+//!     fn message(&self) -> Self::Message {
+//!         "the username is taken".into()
+//!     }
+//!
+//!     async fn call(&mut self, data: &mut Value) -> bool {
+//!         match data {
+//!             Value::String(name) => !self.0.contains(name),
+//!             _ => false,
+//!         }
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn run() {
+//! let taken = Arc::new(HashSet::from(["admin".to_string()]));
+//!
+//! let validator = Validator::new().rule(
+//!     "0",
+//!     Required.and_async(Unique(taken).timeout(Duration::from_secs(1), "try it later".into())),
+//! );
+//!
+//! validator.validate_async(("foo",)).await.unwrap();
+//!
+//! let err = validator.validate_async(("admin",)).await.unwrap_err();
+//! assert_eq!(err.get("0").unwrap()[0].to_string(), "the username is taken");
+//! # }
+//! # run();
+//! # }
+//! ```
+//!
+//! [`Validator::validate_async`]: crate::Validator::validate_async
+//! [`Validator::validate_mut_async`]: crate::Validator::validate_mut_async
+
+use std::{fmt::Debug, future::Future, pin::Pin, time::Duration};
+
+use crate::{Value, ValueMap};
+
+use super::{
+    boxed::{BoxedRule, ErasedRule},
+    CoreRule, RuleInfo, RuleList,
+};
+
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// used by implementation async rules, it is similar to [`Rule`]
+///
+/// [`Rule`]: crate::Rule
+pub trait AsyncRule: Clone + Send + Sync + 'static {
+    /// custom define returning message type
+    type Message;
+
+    /// Named rule type, used to distinguish different rules
+    ///
+    /// allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
+    const NAME: &'static str;
+
+    /// Default rule error message, when validate fails, return the message to user
+    fn message(&self) -> Self::Message;
+
+    /// error message when the field is not found in the data, default is [`AsyncRule::message`]
+    fn missing_message(&self) -> Self::Message {
+        self.message()
+    }
+
+    /// Rule specific implementation, data is gived type all field's value, and current field index.
+    ///
+    /// it returns false when the field is not found
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> impl Future<Output = bool> + Send {
+        async move {
+            match data.current_mut() {
+                Some(value) => self.call(value).await,
+                None => false,
+            }
+        }
+    }
+
+    /// Rule specific implementation, data is current field's value
+    fn call(&mut self, data: &mut Value) -> impl Future<Output = bool> + Send;
+
+    /// when the rule is not finished within the duration, it fails with the `message`,
+    /// it is reported under the name of the rule.
+    ///
+    /// it depends on the tokio timer, so it should be run in tokio runtime.
+    fn timeout(self, duration: Duration, message: Self::Message) -> Timeout<Self>
+    where
+        Self::Message: Clone + Send + Sync,
+    {
+        Timeout {
+            rule: self,
+            duration,
+            message,
+            is_timeout: false,
+        }
+    }
+}
+
+/// async rule with time limit, it is created by [`AsyncRule::timeout`]
+pub struct Timeout<R: AsyncRule> {
+    rule: R,
+    duration: Duration,
+    /// the message of timeout
+    message: R::Message,
+    is_timeout: bool,
+}
+
+impl<R> Clone for Timeout<R>
+where
+    R: AsyncRule,
+    R::Message: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            rule: self.rule.clone(),
+            duration: self.duration,
+            message: self.message.clone(),
+            is_timeout: self.is_timeout,
+        }
+    }
+}
+
+impl<R> Debug for Timeout<R>
+where
+    R: AsyncRule + Debug,
+    R::Message: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Timeout")
+            .field("rule", &self.rule)
+            .field("duration", &self.duration)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl<R> AsyncRule for Timeout<R>
+where
+    R: AsyncRule,
+    R::Message: Clone + Send + Sync,
+{
+    type Message = R::Message;
+
+    const NAME: &'static str = R::NAME;
+
+    fn message(&self) -> Self::Message {
+        if self.is_timeout {
+            self.message.clone()
+        } else {
+            self.rule.message()
+        }
+    }
+
+    fn missing_message(&self) -> Self::Message {
+        self.rule.missing_message()
+    }
+
+    async fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        match tokio::time::timeout(self.duration, self.rule.call_with_relate(data)).await {
+            Ok(res) => res,
+            Err(_) => {
+                self.is_timeout = true;
+                false
+            }
+        }
+    }
+
+    async fn call(&mut self, data: &mut Value) -> bool {
+        match tokio::time::timeout(self.duration, self.rule.call(data)).await {
+            Ok(res) => res,
+            Err(_) => {
+                self.is_timeout = true;
+                false
+            }
+        }
+    }
+}
+
+pub(super) struct AsyncRuleIntoBoxed<R>(R);
+
+impl<R> BoxedRule<ValueMap, R::Message> for AsyncRuleIntoBoxed<R>
+where
+    R: AsyncRule,
+    R::Message: 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<ValueMap, R::Message>> {
+        Box::new(AsyncRuleIntoBoxed(self.0.clone()))
+    }
+
+    /// the async rule can not be called by sync validating, a faked result is misleading
+    fn call(&self, _data: &mut ValueMap) -> Result<(), R::Message> {
+        panic!(
+            "async rule `{}` is used in sync validate, use `validate_async` instead",
+            R::NAME
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        R::NAME
+    }
+
+    fn info(&self) -> RuleInfo {
+        RuleInfo::new(R::NAME).asynchronous()
+    }
+
+    fn call_async<'a>(
+        &'a self,
+        data: &'a mut ValueMap,
+    ) -> Option<BoxFuture<'a, Result<(), R::Message>>> {
+        let mut rule = self.0.clone();
+        Some(Box::pin(async move {
+            if rule.call_with_relate(data).await {
+                Ok(())
+            } else if data.current().is_none() {
+                Err(rule.missing_message())
+            } else {
+                Err(rule.message())
+            }
+        }))
+    }
+}
+
+impl<M> ErasedRule<ValueMap, M> {
+    pub(super) fn new_async<R>(rule: R) -> Self
+    where
+        R: AsyncRule<Message = M>,
+        M: 'static,
+    {
+        Self(Box::new(AsyncRuleIntoBoxed(rule)))
+    }
+}
+
+/// Rule extension, it can coupling sync rule and async rule, such as
+/// ```rust,ignore
+/// Required.and_async(Unique(repo))
+/// ```
+pub trait AsyncRuleExt<Msg> {
+    fn and_async<R>(self, other: R) -> RuleList<ValueMap, Msg>
+    where
+        R: AsyncRule<Message = Msg>;
+}
+
+impl<R, Msg> AsyncRuleExt<Msg> for R
+where
+    R: CoreRule<ValueMap, (), Message = Msg>,
+    Msg: 'static,
+{
+    fn and_async<R2>(self, other: R2) -> RuleList<ValueMap, Msg>
+    where
+        R2: AsyncRule<Message = Msg>,
+    {
+        RuleList {
+            list: vec![ErasedRule::new(self), ErasedRule::new_async(other)],
+            ..Default::default()
+        }
+    }
+}
+
+/// load async rule
+///
+/// ```rust,ignore
+/// Validator::new().rule("username", async_rule(Unique(repo)).and(Length(3..=20)));
+/// ```
+pub fn async_rule<R, M>(rule: R) -> RuleList<ValueMap, M>
+where
+    R: AsyncRule<Message = M>,
+    M: 'static,
+{
+    RuleList {
+        list: vec![ErasedRule::new_async(rule)],
+        ..Default::default()
+    }
+}
+
+impl<M> RuleList<ValueMap, M> {
+    /// append an async rule
+    pub fn and_async<R>(mut self, other: R) -> Self
+    where
+        R: AsyncRule<Message = M>,
+        M: 'static,
+    {
+        self.list.push(ErasedRule::new_async(other));
+        self
+    }
+
+    #[must_use]
+    pub(crate) async fn call_async(&self, data: &mut ValueMap) -> Vec<(&'static str, usize, M)> {
+        let mut msg = Vec::with_capacity(self.list.len());
+
+        for (index, endpoint) in self.iter().enumerate() {
+            let res = match endpoint.call_async(data) {
+                Some(fut) => Some(fut.await),
+                None => None,
+            };
+            match res {
+                Some(res) => {
+                    let _ = res.map_err(|e| msg.push((endpoint.name(), self.instance(index), e)));
+                }
+                None => msg.extend(self.call_endpoint(index, data)),
+            }
+
+            if data.is_excluded() {
+                break;
+            }
+
+            // a missing field only reports the first message
+            if (self.is_bail || data.current().is_none()) && !msg.is_empty() {
+                msg.truncate(1);
+                return msg;
+            }
+        }
+
+        msg.shrink_to_fit();
+        msg
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        available::{Message, Required, StartWith},
+        AsyncRuleExt, RuleExt, Validator,
+    };
+
+    /// in-memory stand-in of user repository
+    #[derive(Clone, Default)]
+    struct Repository {
+        names: Arc<Mutex<HashSet<String>>>,
+        delay: Duration,
+    }
+
+    impl Repository {
+        fn new<const N: usize>(names: [&str; N]) -> Self {
+            Self {
+                names: Arc::new(Mutex::new(names.map(String::from).into())),
+                delay: Duration::ZERO,
+            }
+        }
+
+        async fn exists(&self, name: &str) -> bool {
+            tokio::time::sleep(self.delay).await;
+            self.names.lock().unwrap().contains(name)
+        }
+    }
+
+    #[derive(Clone)]
+    struct Unique(Repository);
+
+    impl AsyncRule for Unique {
+        type Message = Message;
+
+        const NAME: &'static str = "unique";
+
+        fn message(&self) -> Self::Message {
+            "the name is taken".into()
+        }
+
+        async fn call(&mut self, data: &mut Value) -> bool {
+            match data {
+                Value::String(name) => !self.0.exists(name).await,
+                _ => false,
+            }
+        }
+    }
+
+    #[derive(Serialize, serde::Deserialize, Debug)]
+    struct User {
+        name: String,
+        nickname: String,
+    }
+
+    fn user(name: &str, nickname: &str) -> User {
+        User {
+            name: name.into(),
+            nickname: nickname.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_async() {
+        let repo = Repository::new(["admin", "root"]);
+        let validator = Validator::new()
+            .rule("name", Required.and_async(Unique(repo.clone())))
+            .rule("nickname", async_rule(Unique(repo)).and(StartWith("nick_")));
+
+        // the future is able to be spawned, e.g. in axum handler
+        fn assert_send<T: Send>(t: T) -> T {
+            t
+        }
+        assert_send(validator.validate_async(user("foo", "nick_bar")))
+            .await
+            .unwrap();
+
+        let err = validator
+            .validate_async(user("admin", "root"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.get("name").unwrap().len(), 1);
+        assert_eq!(
+            err.get("nickname").unwrap(),
+            &vec![
+                Message::from("the name is taken"),
+                Message::new(crate::available::MessageKind::StartWith("nick_".into()))
+            ]
+        );
+
+        let res = validator
+            .validate_mut_async(user("foo", "nick_bar"))
+            .await
+            .unwrap();
+        assert_eq!(res.name, "foo");
+    }
+
+    #[tokio::test]
+    async fn test_async_bail() {
+        let repo = Repository::new(["admin"]);
+        let validator = Validator::new()
+            .rule("name", Required.and_async(Unique(repo.clone())))
+            .rule("nickname", async_rule(Unique(repo)).and(StartWith("nick_")))
+            .bail();
+
+        let err = validator
+            .validate_async(user("admin", "admin"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err.total(), 1);
+        assert!(err.get("name").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let repo = Repository {
+            delay: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let validator = Validator::new()
+            .rule(
+                "name",
+                async_rule(
+                    Unique(repo.clone()).timeout(Duration::from_millis(10), "timeout".into()),
+                ),
+            )
+            .rule(
+                "nickname",
+                async_rule(Unique(repo).timeout(Duration::from_secs(5), "timeout".into())),
+            );
+
+        let err = validator
+            .validate_async(user("foo", "bar"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert_eq!(err.get("name").unwrap()[0].to_string(), "timeout");
+    }
+
+    #[test]
+    #[should_panic = "async rule `unique` is used in sync validate, use `validate_async` instead"]
+    fn test_sync_validate() {
+        let validator = Validator::new().rule("name", async_rule(Unique(Repository::default())));
+        let _ = validator.validate(user("foo", "bar"));
+    }
+
+    #[tokio::test]
+    async fn test_async_shared() {
+        use crate::available::MessageKind;
+
+        struct Blocked(&'static str);
+
+        let repo = Repository::new(["admin"]);
+        let validator = Validator::new()
+            .rule(
+                "name",
+                async_rule(Unique(repo)).custom_with_context(
+                    |name: &mut String, blocked: &Blocked| {
+                        if name == blocked.0 {
+                            Err(Message::from("the name is blocked"))
+                        } else {
+                            Ok(())
+                        }
+                    },
+                ),
+            )
+            .deny_unknown_fields(MessageKind::UnknownField);
+
+        let err = validator
+            .validate_async_with_context(user("foo", "bar"), Blocked("foo"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.len(), 2);
+        assert_eq!(
+            err.get("name").unwrap()[0].to_string(),
+            "the name is blocked"
+        );
+        assert_eq!(
+            err.get("nickname").unwrap()[0].kind(),
+            &MessageKind::UnknownField
+        );
+    }
+
+    #[test]
+    #[should_panic = "async rule can not be composed by `any_of`"]
+    fn test_async_in_combinator() {
+        let _ = Validator::new().rule(
+            "name",
+            crate::any_of(async_rule(Unique(Repository::default())).and(StartWith("foo"))),
+        );
+    }
+}
@@ -26,7 +26,7 @@
 
 use std::fmt::Display;
 
-use crate::{register::FieldNames, Rule, Value, ValueMap};
+use crate::{register::FieldNames, rule::BoundParam, Rule, Value, ValueMap};
 
 use super::{Message, MessageKind};
 
@@ -57,6 +57,9 @@ macro_rules! impl_compare {
                 // greater
                 Message::new(MessageKind::Compare($label.into(), self.0.to_string()))
             }
+            fn params_in(&self) -> Vec<(&'static str, Value)> {
+                vec![("other", Value::String(self.0.to_string()))]
+            }
         }
     };
 }
@@ -66,83 +69,45 @@ impl_compare!(Elt<T>, "less and equal");
 impl_compare!(Gt<T>, "greater");
 impl_compare!(Egt<T>, "greater and equal");
 
-impl Rule for Lt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "lt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
-
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
-
-        value.current().unwrap() < target.unwrap()
-    }
-
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
-
-impl Rule for Elt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "elt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
-
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
+/// comparing with other field, the field name is `&str` or `String`
+macro_rules! impl_compare_field {
+    ($ident:ident, $name:literal, $op:tt) => {
+        impl_compare_field!($ident<&str>, $name, $op);
+        impl_compare_field!($ident<String>, $name, $op);
+    };
+    ($type:ty, $name:literal, $op:tt) => {
+        impl Rule for $type {
+            type Message = Message;
 
-        value.current().unwrap() <= target.unwrap()
-    }
+            const NAME: &'static str = $name;
 
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
-impl Rule for Gt<&str> {
-    type Message = Message;
+            fn message(&self) -> Self::Message {
+                self.message_in()
+            }
 
-    const NAME: &'static str = "gt";
+            crate::__impl_missing_message!();
 
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                self.params_in()
+            }
 
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
+            fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
+                let target = self.get_target_value(value);
 
-        value.current().unwrap() > target.unwrap()
-    }
+                matches!((value.current(), target), (Some(value), Some(target)) if value $op target)
+            }
 
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
+            fn call(&mut self, _value: &mut Value) -> bool {
+                unreachable!()
+            }
+        }
+    };
 }
-impl Rule for Egt<&str> {
-    type Message = Message;
-
-    const NAME: &'static str = "egt";
-
-    fn message(&self) -> Self::Message {
-        self.message_in()
-    }
 
-    fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-        let target = self.get_target_value(value);
-
-        value.current().unwrap() >= target.unwrap()
-    }
-
-    fn call(&mut self, _value: &mut Value) -> bool {
-        unreachable!()
-    }
-}
+impl_compare_field!(Lt, "lt", <);
+impl_compare_field!(Elt, "elt", <=);
+impl_compare_field!(Gt, "gt", >);
+impl_compare_field!(Egt, "egt", >=);
 
 macro_rules! impl_lt_num {
     ($ty:ty) => {
@@ -155,8 +120,14 @@ macro_rules! impl_lt_num {
                 self.message_in()
             }
 
+            crate::__impl_missing_message!();
+
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                vec![("other", BoundParam::into_param(self.0))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-                value.current().unwrap() < self.0
+                value.current().is_some_and(|value| value < self.0)
             }
 
             fn call(&mut self, _value: &mut Value) -> bool {
@@ -184,8 +155,14 @@ macro_rules! impl_elt_num {
                 self.message_in()
             }
 
+            crate::__impl_missing_message!();
+
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                vec![("other", BoundParam::into_param(self.0))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-                value.current().unwrap() <= self.0
+                value.current().is_some_and(|value| value <= self.0)
             }
 
             fn call(&mut self, _value: &mut Value) -> bool {
@@ -213,8 +190,14 @@ macro_rules! impl_gt_num {
                 self.message_in()
             }
 
+            crate::__impl_missing_message!();
+
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                vec![("other", BoundParam::into_param(self.0))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-                value.current().unwrap() > self.0
+                value.current().is_some_and(|value| value > self.0)
             }
 
             fn call(&mut self, _value: &mut Value) -> bool {
@@ -241,8 +224,14 @@ macro_rules! impl_egt_num {
                 self.message_in()
             }
 
+            crate::__impl_missing_message!();
+
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                vec![("other", BoundParam::into_param(self.0))]
+            }
+
             fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
-                value.current().unwrap() >= self.0
+                value.current().is_some_and(|value| value >= self.0)
             }
 
             fn call(&mut self, _value: &mut Value) -> bool {
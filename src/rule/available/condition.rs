@@ -0,0 +1,584 @@
+//! Conditional rules, they depend on the value of other fields.
+//!
+//! - [`RequiredIf`] the field is required when other field is equal to the value
+//! - [`RequiredUnless`] the field is required unless other field is equal to the value
+//! - [`RequiredWith`] the field is required when other field is present
+//! - [`RequiredWithout`] the field is required when other field is not present
+//! - [`Prohibited`] the field must be empty or not present
+//! - [`ExcludeIf`] the rest rules of the field are skipped when other field is equal to the value
+//! - [`When`] the wrapped rule is only called when the predicate returns true
+//!
+//! A field is not present when it is not found, `None`, `()`, empty string, empty array or empty map,
+//! and the other field with format error, e.g. `"address."`, is not present either.
+//!
+//! numbers are compared by their values, e.g. `RequiredIf("age", 18)` is matching `age: u8`,
+//! other values should be the same type as the other field.
+//!
+//! the other field is `&'static str` by default, `String` is also supported,
+//! e.g. `RequiredIf(String::from("age"), 18)` for the field name from config.
+//!
+//! # Examples
+//! ```
+//! # use serde::Serialize;
+//! # use valitron::{available::{ExcludeIf, MessageKind, RequiredIf, StartWith}, RuleExt, Validatable, Validator};
+//! #[derive(Serialize, Debug)]
+//! struct Order {
+//!     country: &'static str,
+//!     vat_number: String,
+//! }
+//!
+//! let validator = Validator::new()
+//!     .rule("vat_number", ExcludeIf("country", "US").and(RequiredIf("country", "DE")).and(StartWith("DE")));
+//!
+//! let order = Order {
+//!     country: "DE",
+//!     vat_number: String::new(),
+//! };
+//! let err = order.validate(&validator).unwrap_err();
+//! assert!(matches!(
+//!     err.get("vat_number").unwrap()[0].kind(),
+//!     MessageKind::RequiredIf(_, _)
+//! ));
+//!
+//! let order = Order {
+//!     country: "US",
+//!     vat_number: String::new(),
+//! };
+//! order.validate(&validator).unwrap();
+//! ```
+
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+};
+
+use serde::Serialize;
+
+use crate::{register::FieldNames, ser::Serializer, Rule, Value, ValueMap};
+
+use super::{Message, MessageKind};
+
+/// check the field is not present
+fn is_empty(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Unit) => true,
+        Some(Value::Option(opt)) => is_empty(opt.as_ref().as_ref()),
+        Some(Value::String(s)) => s.is_empty(),
+        Some(Value::Array(arr)) => arr.is_empty(),
+        Some(Value::Map(map)) => map.is_empty(),
+        Some(_) => false,
+    }
+}
+
+/// the other field with format error is not present, instead of panic on every validation
+fn get_target<'v>(data: &'v ValueMap, field: &str) -> Option<&'v Value> {
+    let names = FieldNames::from(field);
+    names.check().ok()?;
+    data.get(&names)
+}
+
+/// the number of any type, integers are compared exactly, also with floats
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (*self, *other) {
+            (Number::Int(a), Number::Int(b)) => Some(a.cmp(&b)),
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(&b),
+            (Number::Int(a), Number::Float(b)) => cmp_int_float(a, b),
+            (Number::Float(a), Number::Int(b)) => cmp_int_float(b, a).map(Ordering::reverse),
+        }
+    }
+}
+
+/// the integer is not cast to float, it is inexact above 2^53
+fn cmp_int_float(int: i128, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    }
+    let floor = float.floor();
+    if floor < i128::MIN as f64 {
+        return Some(Ordering::Greater);
+    }
+    if floor >= i128::MAX as f64 {
+        return Some(Ordering::Less);
+    }
+    match int.cmp(&(floor as i128)) {
+        Ordering::Equal if floor < float => Some(Ordering::Less),
+        ord => Some(ord),
+    }
+}
+
+pub(crate) fn number(value: &Value) -> Option<Number> {
+    let number = match value {
+        Value::Uint8(n) => Number::Int(*n as i128),
+        Value::Int8(n) => Number::Int(*n as i128),
+        Value::Uint16(n) => Number::Int(*n as i128),
+        Value::Int16(n) => Number::Int(*n as i128),
+        Value::Uint32(n) => Number::Int(*n as i128),
+        Value::Int32(n) => Number::Int(*n as i128),
+        Value::Uint64(n) => Number::Int(*n as i128),
+        Value::Int64(n) => Number::Int(*n as i128),
+        Value::Float32(n) => Number::Float(n.get() as f64),
+        Value::Float64(n) => Number::Float(n.get()),
+        _ => return None,
+    };
+    Some(number)
+}
+
+/// numbers are equal by their values, e.g. `18_u8` and `18_i64`
+fn is_same(target: &Value, value: &Value) -> bool {
+    match (number(target), number(value)) {
+        (Some(a), Some(b)) => a == b,
+        _ => target == value,
+    }
+}
+
+/// check the other field is equal to the value, `Some(value)` is also matching.
+fn is_equal<V: Serialize>(data: &ValueMap, field: &str, value: &V) -> bool {
+    let target = match get_target(data, field) {
+        Some(target) => target,
+        None => return false,
+    };
+    let value = match value.serialize(Serializer) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+
+    match target {
+        Value::Option(opt) if !matches!(value, Value::Option(_)) => opt
+            .as_ref()
+            .as_ref()
+            .is_some_and(|target| is_same(target, &value)),
+        target => is_same(target, &value),
+    }
+}
+
+/// The field is required when other field is equal to the value
+#[derive(Clone)]
+pub struct RequiredIf<V, F = &'static str>(pub F, pub V);
+
+/// The field is required unless other field is equal to the value
+#[derive(Clone)]
+pub struct RequiredUnless<V, F = &'static str>(pub F, pub V);
+
+/// The field is required when other field is present
+#[derive(Clone, Copy, Debug)]
+pub struct RequiredWith<F = &'static str>(pub F);
+
+/// The field is required when other field is not present
+#[derive(Clone, Copy, Debug)]
+pub struct RequiredWithout<F = &'static str>(pub F);
+
+/// The field must be empty or not present
+#[derive(Clone, Copy, Debug)]
+pub struct Prohibited;
+
+/// The rest rules of the field are skipped when other field is equal to the value,
+/// so it should be the first rule of the field.
+///
+/// this always return true
+#[derive(Clone)]
+pub struct ExcludeIf<V, F = &'static str>(pub F, pub V);
+
+/// The wrapped rule is only called when the predicate returns true
+///
+/// its name is `when`, it is the message key, e.g. `vat_number.when`,
+/// so it is not mixed up with the same rule out of the condition.
+///
+/// ```
+/// # use valitron::{available::{Required, When}, Validator, ValueMap};
+/// let validator = Validator::new().rule(
+///     "vat_number",
+///     When(
+///         |data: &ValueMap| data.get(&"country".into()).is_some(),
+///         Required,
+///     ),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct When<P, R>(pub P, pub R);
+
+macro_rules! impl_debug {
+    ($ident:ident) => {
+        impl<V: Debug, F: Debug> Debug for $ident<V, F> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($ident))
+                    .field(&self.0)
+                    .field(&self.1)
+                    .finish()
+            }
+        }
+    };
+}
+
+impl_debug!(RequiredIf);
+impl_debug!(RequiredUnless);
+impl_debug!(ExcludeIf);
+
+impl<V, F> Rule for RequiredIf<V, F>
+where
+    F: AsRef<str> + Clone + Send + Sync,
+    V: Serialize + Display + Clone + Send + Sync,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "required_if";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::RequiredIf(
+            self.0.as_ref().into(),
+            self.1.to_string(),
+        ))
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            ("other", Value::String(self.0.as_ref().to_owned())),
+            ("value", self.1.serialize(Serializer).unwrap_or(Value::Unit)),
+        ]
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        !is_equal(data, self.0.as_ref(), &self.1) || !is_empty(data.current())
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+impl<V, F> Rule for RequiredUnless<V, F>
+where
+    F: AsRef<str> + Clone + Send + Sync,
+    V: Serialize + Display + Clone + Send + Sync,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "required_unless";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::RequiredUnless(
+            self.0.as_ref().into(),
+            self.1.to_string(),
+        ))
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            ("other", Value::String(self.0.as_ref().to_owned())),
+            ("value", self.1.serialize(Serializer).unwrap_or(Value::Unit)),
+        ]
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        is_equal(data, self.0.as_ref(), &self.1) || !is_empty(data.current())
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+impl<F> Rule for RequiredWith<F>
+where
+    F: AsRef<str> + Clone + Send + Sync,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "required_with";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::RequiredWith(self.0.as_ref().into()))
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![("other", Value::String(self.0.as_ref().to_owned()))]
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        is_empty(get_target(data, self.0.as_ref())) || !is_empty(data.current())
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+impl<F> Rule for RequiredWithout<F>
+where
+    F: AsRef<str> + Clone + Send + Sync,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "required_without";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::RequiredWithout(self.0.as_ref().into()))
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![("other", Value::String(self.0.as_ref().to_owned()))]
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        !is_empty(get_target(data, self.0.as_ref())) || !is_empty(data.current())
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+impl Rule for Prohibited {
+    type Message = Message;
+
+    const NAME: &'static str = "prohibited";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::Prohibited)
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        is_empty(data.current())
+    }
+
+    fn call(&mut self, value: &mut Value) -> bool {
+        is_empty(Some(value))
+    }
+}
+
+impl<V, F> Rule for ExcludeIf<V, F>
+where
+    F: AsRef<str> + Clone + Send + Sync,
+    V: Serialize + Clone + Send + Sync,
+{
+    type Message = Message;
+
+    const NAME: &'static str = "exclude_if";
+
+    fn message(&self) -> Self::Message {
+        Message::new(MessageKind::ExcludeIf)
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            ("other", Value::String(self.0.as_ref().to_owned())),
+            ("value", self.1.serialize(Serializer).unwrap_or(Value::Unit)),
+        ]
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        if is_equal(data, self.0.as_ref(), &self.1) {
+            data.exclude();
+        }
+        true
+    }
+
+    fn call(&mut self, _value: &mut Value) -> bool {
+        unreachable!()
+    }
+}
+
+impl<P, R> Rule for When<P, R>
+where
+    P: Fn(&ValueMap) -> bool + Clone + Send + Sync,
+    R: Rule,
+{
+    type Message = R::Message;
+
+    const NAME: &'static str = "when";
+
+    fn message(&self) -> Self::Message {
+        self.1.message()
+    }
+
+    fn missing_message(&self) -> Self::Message {
+        self.1.missing_message()
+    }
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.1.params()
+    }
+
+    fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
+        !(self.0)(data) || self.1.call_with_relate(data)
+    }
+
+    fn call(&mut self, value: &mut Value) -> bool {
+        self.1.call(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    use crate::{ser::to_value, RuleExt, Validator};
+
+    #[derive(Serialize)]
+    struct Input {
+        country: &'static str,
+        age: Option<u8>,
+        vat_number: String,
+        company: Option<String>,
+        coupon: &'static str,
+    }
+
+    fn value_map(input: Input, field: &str) -> ValueMap {
+        let mut map = ValueMap::new(to_value(input).unwrap());
+        map.index(field.into());
+        map
+    }
+
+    fn input() -> Input {
+        Input {
+            country: "DE",
+            age: Some(18),
+            vat_number: String::new(),
+            company: None,
+            coupon: "",
+        }
+    }
+
+    #[test]
+    fn test_required_if() {
+        let mut map = value_map(input(), "vat_number");
+        assert!(!RequiredIf("country", "DE").call_with_relate(&mut map));
+        assert!(RequiredIf("country", "US").call_with_relate(&mut map));
+        assert!(!RequiredIf("age", 18_u8).call_with_relate(&mut map));
+        assert!(!RequiredIf("age", 18_i64).call_with_relate(&mut map));
+        assert!(!RequiredIf("age", 18.0).call_with_relate(&mut map));
+        assert!(RequiredIf("age", 19_i64).call_with_relate(&mut map));
+        assert!(RequiredIf("age", 18.5).call_with_relate(&mut map));
+        assert!(!RequiredIf(String::from("country"), "DE").call_with_relate(&mut map));
+        assert!(RequiredIf("not_found", "DE").call_with_relate(&mut map));
+
+        let mut map = value_map(
+            Input {
+                vat_number: "DE123".into(),
+                ..input()
+            },
+            "vat_number",
+        );
+        assert!(RequiredIf("country", "DE").call_with_relate(&mut map));
+    }
+
+    #[test]
+    fn test_number() {
+        let big = 1_i128 << 53;
+        assert!(Number::Int(big + 1) > Number::Float(big as f64));
+        assert!(Number::Float(big as f64) < Number::Int(big + 1));
+        assert!(Number::Int(big) == Number::Float(big as f64));
+        assert!(Number::Int(1) < Number::Float(1.5));
+        assert!(Number::Int(-1) > Number::Float(-1.5));
+        assert!(Number::Int(i128::MAX) < Number::Float(f64::INFINITY));
+        assert!(Number::Int(i128::MIN) > Number::Float(f64::NEG_INFINITY));
+        assert_eq!(Number::Int(0).partial_cmp(&Number::Float(f64::NAN)), None);
+    }
+
+    #[test]
+    fn test_required_unless() {
+        let mut map = value_map(input(), "vat_number");
+        assert!(RequiredUnless("country", "DE").call_with_relate(&mut map));
+        assert!(!RequiredUnless("country", "US").call_with_relate(&mut map));
+    }
+
+    #[test]
+    fn test_required_with() {
+        let mut map = value_map(input(), "vat_number");
+        assert!(!RequiredWith("country").call_with_relate(&mut map));
+        assert!(RequiredWith("company").call_with_relate(&mut map));
+        assert!(RequiredWith("coupon").call_with_relate(&mut map));
+
+        assert!(RequiredWithout("country").call_with_relate(&mut map));
+        assert!(!RequiredWithout("company").call_with_relate(&mut map));
+        assert!(!RequiredWithout("not_found").call_with_relate(&mut map));
+
+        // the invalid field is not present, it does not panic
+        assert!(RequiredWith("country.").call_with_relate(&mut map));
+        assert!(!RequiredWithout("x[").call_with_relate(&mut map));
+        assert!(RequiredIf("a.", "DE").call_with_relate(&mut map));
+        assert!(!RequiredUnless("a.", "DE").call_with_relate(&mut map));
+    }
+
+    #[test]
+    fn test_prohibited() {
+        let mut map = value_map(input(), "company");
+        assert!(Prohibited.call_with_relate(&mut map));
+
+        let mut map = value_map(input(), "country");
+        assert!(!Prohibited.call_with_relate(&mut map));
+    }
+
+    #[test]
+    fn test_exclude_if() {
+        let validator = Validator::new()
+            .rule(
+                "vat_number",
+                ExcludeIf("country", "DE").and(RequiredIf("age", 18_u8)),
+            )
+            .rule(
+                "coupon",
+                ExcludeIf("country", "US").and(super::super::Required),
+            );
+
+        let err = validator.validate(input()).unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert!(matches!(
+            err.get("coupon").unwrap()[0].kind(),
+            MessageKind::Required
+        ));
+    }
+
+    #[test]
+    fn test_when() {
+        let validator = Validator::new().rule(
+            "vat_number",
+            When(
+                |data: &ValueMap| data.get(&"country".into()) == Some(&Value::String("DE".into())),
+                super::super::Required,
+            ),
+        );
+
+        let err = validator.validate(input()).unwrap_err();
+        assert!(matches!(
+            err.get("vat_number").unwrap()[0].kind(),
+            MessageKind::Required
+        ));
+
+        validator
+            .validate(Input {
+                country: "US",
+                ..input()
+            })
+            .unwrap();
+
+        // both are kept, the message of condition is registered by `when`
+        let err = Validator::new()
+            .rule(
+                "vat_number",
+                super::super::Required.and(When(|_: &ValueMap| true, super::super::Required)),
+            )
+            .message([("vat_number.when", "vat number is required in DE")])
+            .validate(input())
+            .unwrap_err();
+        assert_eq!(err.get("vat_number").unwrap().len(), 2);
+        assert_eq!(
+            err.get("vat_number").unwrap()[1].to_string(),
+            "vat number is required in DE"
+        );
+    }
+}
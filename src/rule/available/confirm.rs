@@ -81,6 +81,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(MessageKind::Confirm(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Vec<(&'static str, Value)> {
+        vec![("other", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for Confirm<String> {
@@ -92,10 +96,16 @@ impl Rule for Confirm<String> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
         let target = self.get_target_value(value);
 
-        value.current().unwrap() == target.unwrap()
+        matches!((value.current(), target), (Some(value), Some(target)) if value == target)
     }
 
     fn call(&mut self, _value: &mut Value) -> bool {
@@ -112,10 +122,16 @@ impl Rule for Confirm<&str> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call_with_relate(&mut self, value: &mut ValueMap) -> bool {
         let target = self.get_target_value(value);
 
-        value.current().unwrap() == target.unwrap()
+        matches!((value.current(), target), (Some(value), Some(target)) if value == target)
     }
 
     fn call(&mut self, _value: &mut Value) -> bool {
@@ -190,6 +206,6 @@ mod tests {
         let mut map = ValueMap::new(all_value);
         map.index(FieldNames::new("other_name".to_string()));
         let res = confirm.call_with_relate(&mut map);
-        assert!(res == false);
+        assert!(!res);
     }
 }
@@ -81,6 +81,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::Contains(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Vec<(&'static str, Value)> {
+        vec![("substring", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for Contains<&str> {
@@ -92,6 +96,12 @@ impl Rule for Contains<&str> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(self.0),
@@ -109,6 +119,12 @@ impl Rule for Contains<String> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(&self.0),
@@ -126,6 +142,12 @@ impl Rule for Contains<char> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.contains(self.0),
@@ -59,6 +59,8 @@ impl Rule for Email {
         Message::new(super::MessageKind::Email)
     }
 
+    crate::__impl_missing_message!();
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => validate_email(s),
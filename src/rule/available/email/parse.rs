@@ -92,14 +92,14 @@ impl<'a> Cursor<'a> {
                 }
             }
         } else if self.token.len() == 1 {
-            return match char {
+            match char {
                 '@' => {
                     self.token.push(EmailToken::At);
                     self.at_index = start_usize;
                     Some(EmailToken::At)
                 }
                 _ => None,
-            };
+            }
         } else {
             match char {
                 'a'..='z' | 'A'..='Z' | '0'..='9' => {
@@ -128,11 +128,11 @@ impl<'a> Cursor<'a> {
                 }
                 '.' => {
                     self.token.push(EmailToken::Dot);
-                    return Some(EmailToken::Dot);
+                    Some(EmailToken::Dot)
                 }
                 '@' => {
                     self.token.push(EmailToken::At);
-                    return Some(EmailToken::At);
+                    Some(EmailToken::At)
                 }
                 '[' => {
                     if start_usize != self.at_index + 1 {
@@ -155,7 +155,7 @@ impl<'a> Cursor<'a> {
                     self.is_ip = true;
                     self.token.push(EmailToken::Ip);
 
-                    return Some(EmailToken::Ip);
+                    Some(EmailToken::Ip)
                 }
                 c if !c.is_ascii() => {
                     let domain = &self.email_str[self.at_index + 1..];
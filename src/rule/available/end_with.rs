@@ -70,6 +70,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::EndsWith(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Vec<(&'static str, Value)> {
+        vec![("suffix", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for EndsWith<&str> {
@@ -81,6 +85,12 @@ impl Rule for EndsWith<&str> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(self.0),
@@ -98,6 +108,12 @@ impl Rule for EndsWith<String> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(&self.0),
@@ -115,6 +131,12 @@ impl Rule for EndsWith<char> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.ends_with(self.0),
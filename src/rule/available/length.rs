@@ -54,7 +54,10 @@
 
 use std::{fmt::Debug, ops::RangeBounds};
 
-use crate::{rule::string::StringRule, Rule, Value};
+use crate::{
+    rule::{range_params, string::StringRule},
+    Rule, Value,
+};
 
 use super::Message;
 
@@ -90,7 +93,7 @@ impl<T> Length<T> {
 
 impl<T> Rule for Length<T>
 where
-    T: RangeBounds<usize> + Clone,
+    T: RangeBounds<usize> + Clone + Send + Sync,
 {
     type Message = Message;
 
@@ -99,6 +102,12 @@ where
     fn message(&self) -> Self::Message {
         self.message_in()
     }
+
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        range_params(&self.0)
+    }
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::String(str) => self.0.contains(&str.len()),
@@ -111,7 +120,7 @@ where
 
 impl<T> StringRule for Length<T>
 where
-    T: RangeBounds<usize> + Clone,
+    T: RangeBounds<usize> + Clone + Send + Sync,
 {
     type Message = Message;
 
@@ -149,6 +158,15 @@ impl Rule for Length<Num> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            ("min", Value::Uint64(self.0 .0 as u64)),
+            ("max", Value::Uint64(self.0 .0 as u64)),
+        ]
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::String(str) => self.0 == str.len(),
@@ -4,7 +4,10 @@ use std::fmt::Display;
 
 use serde::Serialize;
 
+use crate::rule::ClosureMessage;
+
 pub mod compare;
+pub mod condition;
 pub mod confirm;
 pub mod contains;
 pub mod email;
@@ -18,6 +21,9 @@ pub mod start_with;
 pub mod trim;
 
 pub use compare::{Egt, Elt, Gt, Lt};
+pub use condition::{
+    ExcludeIf, Prohibited, RequiredIf, RequiredUnless, RequiredWith, RequiredWithout, When,
+};
 pub use confirm::Confirm;
 pub use contains::Contains;
 pub use email::Email;
@@ -71,6 +77,33 @@ pub enum MessageKind {
     /// as regex rule
     Regex,
 
+    /// as required_if rule, arguments are other field name and value
+    RequiredIf(String, String),
+
+    /// as required_unless rule, arguments are other field name and value
+    RequiredUnless(String, String),
+
+    /// as required_with rule, only one argument is other field name
+    RequiredWith(String),
+
+    /// as required_without rule, only one argument is other field name
+    RequiredWithout(String),
+
+    /// as prohibited rule
+    Prohibited,
+
+    /// as exclude_if rule, it always passes, only mark
+    ExcludeIf,
+
+    /// the field is not found in the data
+    Missing,
+
+    /// the field has not any rule, it is reported by `deny_unknown_fields`
+    UnknownField,
+
+    /// the value or context of closure is not the type, only one argument is the type name
+    Mismatch(String),
+
     /// other way, it used by other type converting Message stopover
     Fallback(String),
 }
@@ -93,6 +126,15 @@ impl Serialize for MessageKind {
             MessageKind::Email => serializer.serialize_str("email"),
             MessageKind::Fallback(s) => serializer.serialize_str(s),
             MessageKind::Regex => serializer.serialize_str("regex"),
+            MessageKind::RequiredIf(_, _) => serializer.serialize_str("required_if"),
+            MessageKind::RequiredUnless(_, _) => serializer.serialize_str("required_unless"),
+            MessageKind::RequiredWith(_) => serializer.serialize_str("required_with"),
+            MessageKind::RequiredWithout(_) => serializer.serialize_str("required_without"),
+            MessageKind::Prohibited => serializer.serialize_str("prohibited"),
+            MessageKind::ExcludeIf => serializer.serialize_str("exclude_if"),
+            MessageKind::Missing => serializer.serialize_str("missing"),
+            MessageKind::UnknownField => serializer.serialize_str("unknown_field"),
+            MessageKind::Mismatch(_) => serializer.serialize_str("mismatch"),
         }
     }
 }
@@ -114,6 +156,21 @@ impl Message {
     pub fn kind(&self) -> &MessageKind {
         &self.kind
     }
+
+    /// replace `{label}` of the fallback content
+    pub(crate) fn replace_label(&mut self, label: &str) {
+        if let MessageKind::Fallback(content) = &mut self.kind {
+            if content.contains("{label}") {
+                *content = content.replace("{label}", label);
+            }
+        }
+    }
+}
+
+impl From<MessageKind> for Message {
+    fn from(kind: MessageKind) -> Self {
+        Self::new(kind)
+    }
 }
 
 impl From<Message> for String {
@@ -135,6 +192,16 @@ impl From<&str> for Message {
     }
 }
 
+impl ClosureMessage for Message {
+    fn missing() -> Self {
+        Message::new(MessageKind::Missing)
+    }
+
+    fn mismatch(expected: &'static str) -> Self {
+        Message::new(MessageKind::Mismatch(expected.to_owned()))
+    }
+}
+
 impl Display for Message {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.kind.fmt(f)
@@ -160,6 +227,27 @@ impl Display for MessageKind {
             MessageKind::Email => "the value is not a email address".fmt(f),
             MessageKind::Fallback(s) => s.fmt(f),
             MessageKind::Regex => "regular matching failed".fmt(f),
+            MessageKind::RequiredIf(other, value) => {
+                write!(f, "this field is required when `{}` is `{}`", other, value)
+            }
+            MessageKind::RequiredUnless(other, value) => {
+                write!(
+                    f,
+                    "this field is required unless `{}` is `{}`",
+                    other, value
+                )
+            }
+            MessageKind::RequiredWith(other) => {
+                write!(f, "this field is required when `{}` is present", other)
+            }
+            MessageKind::RequiredWithout(other) => {
+                write!(f, "this field is required when `{}` is not present", other)
+            }
+            MessageKind::Prohibited => "this field is prohibited".fmt(f),
+            MessageKind::ExcludeIf => "this field is excluded".fmt(f),
+            MessageKind::Missing => "this field is missing".fmt(f),
+            MessageKind::UnknownField => "this field is unknown".fmt(f),
+            MessageKind::Mismatch(ty) => write!(f, "the type `{}` can not be matched", ty),
         }
     }
 }
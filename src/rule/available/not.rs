@@ -56,6 +56,17 @@ impl<T: Rule> Rule for Not<T> {
         self.0.message()
     }
 
+    fn missing_message(&self) -> Self::Message {
+        self.0.missing_message()
+    }
+
+    /// the parameters of inner rule, and `("not", true)`
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        let mut params = self.0.params();
+        params.push(("not", Value::Boolean(true)));
+        params
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         !self.0.call(value)
     }
@@ -29,7 +29,7 @@
 use std::{fmt::Debug, marker::PhantomData, ops::RangeBounds};
 
 use super::Message;
-use crate::{Rule, Value};
+use crate::{rule::range_params, Rule, Value};
 
 #[derive(Clone)]
 pub struct Range<T, Num> {
@@ -67,7 +67,7 @@ macro_rules! impl_range {
     ($val:ident($ty:ty)) => {
         impl<T> Rule for Range<T, $ty>
         where
-            T: RangeBounds<$ty> + Clone,
+            T: RangeBounds<$ty> + Clone + Send + Sync,
         {
             type Message = Message;
 
@@ -77,6 +77,12 @@ macro_rules! impl_range {
                 self.message_in()
             }
 
+            crate::__impl_missing_message!();
+
+            fn params(&self) -> Vec<(&'static str, Value)> {
+                range_params(&self.value)
+            }
+
             fn call(&mut self, data: &mut Value) -> bool {
                 match data {
                     Value::$val(n) => self.value.contains(n),
@@ -99,7 +105,7 @@ impl_range!(Char(char));
 
 impl<T> Rule for Range<T, f32>
 where
-    T: RangeBounds<f32> + Clone + 'static,
+    T: RangeBounds<f32> + Clone + Send + Sync + 'static,
 {
     type Message = Message;
 
@@ -109,6 +115,12 @@ where
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        range_params(&self.value)
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::Float32(f) => self.value.contains(f.as_ref()),
@@ -119,7 +131,7 @@ where
 
 impl<T> Rule for Range<T, f64>
 where
-    T: RangeBounds<f64> + Clone + 'static,
+    T: RangeBounds<f64> + Clone + Send + Sync + 'static,
 {
     type Message = Message;
 
@@ -129,6 +141,12 @@ where
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        range_params(&self.value)
+    }
+
     fn call(&mut self, data: &mut Value) -> bool {
         match data {
             Value::Float64(f) => self.value.contains(f.as_ref()),
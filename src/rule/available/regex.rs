@@ -35,16 +35,19 @@
 //!     .unwrap();
 //! ```
 
-use crate::{rule::string::StringRule, Rule};
+use std::borrow::Cow;
+
+use crate::{rule::string::StringRule, Rule, Value};
 
 use super::Message;
 
 #[derive(Debug, Clone)]
-pub struct Regex<'a>(&'a str);
+pub struct Regex<'a>(Cow<'a, str>);
 
 impl<'a> Regex<'a> {
-    pub fn new(pattern: &'a str) -> Self {
-        Self(pattern)
+    /// the pattern is `&str` or `String`, e.g. the pattern from config
+    pub fn new<P: Into<Cow<'a, str>>>(pattern: P) -> Self {
+        Self(pattern.into())
     }
 }
 
@@ -57,10 +60,16 @@ impl Rule for Regex<'_> {
         Message::new(super::MessageKind::Regex)
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        vec![("pattern", Value::String(self.0.to_string()))]
+    }
+
     fn call(&mut self, data: &mut crate::Value) -> bool {
         match data {
             crate::Value::String(s) => {
-                let reg = regex::Regex::new(self.0)
+                let reg = regex::Regex::new(&self.0)
                     .unwrap_or_else(|_| panic!("regex \"{}\" have syntax error", self.0));
                 reg.is_match(s)
             }
@@ -79,7 +88,7 @@ impl StringRule for Regex<'_> {
     }
 
     fn call(&mut self, data: &mut String) -> bool {
-        let reg = regex::Regex::new(self.0)
+        let reg = regex::Regex::new(&self.0)
             .unwrap_or_else(|_| panic!("regex \"{}\" have syntax error", self.0));
         reg.is_match(data)
     }
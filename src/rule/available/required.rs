@@ -1,5 +1,5 @@
 //! Value can not be empty, supported `Vec`, `String`, `HashMap`
-//! or `BTreeMap`. other types always return true.
+//! or `BTreeMap`, and `Option` can not be `None`. other types always return true.
 //!
 //! # Examples
 //! ```
@@ -65,11 +65,17 @@ impl Rule for Required {
         Message::new(super::MessageKind::Required)
     }
 
+    crate::__impl_missing_message!();
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => !s.is_empty(),
             Value::Array(arr) => !arr.is_empty(),
             Value::Map(map) => !map.is_empty(),
+            Value::Option(opt) => match opt.as_mut() {
+                Some(value) => Rule::call(self, value),
+                None => false,
+            },
             _ => true,
         }
     }
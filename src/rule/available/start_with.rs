@@ -77,6 +77,10 @@ where
     fn message_in(&self) -> Message {
         Message::new(super::MessageKind::StartWith(self.0.to_string()))
     }
+
+    fn params_in(&self) -> Vec<(&'static str, Value)> {
+        vec![("prefix", Value::String(self.0.to_string()))]
+    }
 }
 
 impl Rule for StartWith<&str> {
@@ -88,6 +92,12 @@ impl Rule for StartWith<&str> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(self.0),
@@ -105,6 +115,12 @@ impl Rule for StartWith<String> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(&self.0),
@@ -122,6 +138,12 @@ impl Rule for StartWith<char> {
         self.message_in()
     }
 
+    crate::__impl_missing_message!();
+
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        self.params_in()
+    }
+
     fn call(&mut self, value: &mut Value) -> bool {
         match value {
             Value::String(s) => s.starts_with(self.0),
@@ -45,6 +45,8 @@ impl Rule for Trim {
     fn message(&self) -> Self::Message {
         Message::new(super::MessageKind::Trim)
     }
+
+    crate::__impl_missing_message!();
 }
 
 impl StringRule for Trim {
@@ -70,5 +72,5 @@ fn test_trim() {
     let mut trim = Trim {};
     let _ = Rule::call(&mut trim, &mut value);
 
-    assert!(matches!(value, Value::String(s) if s == "hello".to_string()));
+    assert!(matches!(value, Value::String(s) if s == "hello"));
 }
@@ -1,9 +1,15 @@
 use std::marker::PhantomData;
 
-use super::CoreRule;
+use super::{CoreRule, RuleInfo};
+
+#[cfg(feature = "async")]
+use super::asynchronous::BoxFuture;
 
 pub struct ErasedRule<I, M>(pub(super) Box<dyn BoxedRule<I, M>>);
 
+/// the name of a failed rule, its indexes in the inner rules of combinators and its message
+pub type Failed<M> = (&'static str, Vec<usize>, M);
+
 impl<I, M> ErasedRule<I, M> {
     pub fn new<H, S>(handler: H) -> Self
     where
@@ -17,10 +23,30 @@ impl<I, M> ErasedRule<I, M> {
     pub fn name(&self) -> &'static str {
         self.0.name()
     }
-    pub fn call(&mut self, data: &mut I) -> Result<(), M> {
+    pub fn call(&self, data: &mut I) -> Result<(), M> {
         self.0.call(data)
     }
 
+    /// combinators return the messages of failed inner rules
+    pub fn call_many(&self, data: &mut I) -> Result<(), Vec<Failed<M>>> {
+        self.0.call_many(data)
+    }
+
+    /// the name of the rule and its inner rules
+    pub fn names(&self) -> Vec<&'static str> {
+        self.0.names()
+    }
+
+    pub fn info(&self) -> RuleInfo {
+        self.0.info()
+    }
+
+    /// it returns `None` when the rule is not async
+    #[cfg(feature = "async")]
+    pub fn call_async<'a>(&'a self, data: &'a mut I) -> Option<BoxFuture<'a, Result<(), M>>> {
+        self.0.call_async(data)
+    }
+
     pub fn map<M2>(self, layer: fn(M) -> M2) -> ErasedRule<I, M2>
     where
         M: 'static,
@@ -29,6 +55,25 @@ impl<I, M> ErasedRule<I, M> {
     {
         ErasedRule(Box::new(Map { inner: self, layer }))
     }
+
+    pub fn named(self, name: &'static str) -> Self
+    where
+        M: 'static,
+        I: 'static,
+    {
+        ErasedRule(Box::new(Named { inner: self, name }))
+    }
+
+    pub fn with_message(self, message: M) -> Self
+    where
+        M: Clone + Send + Sync + 'static,
+        I: 'static,
+    {
+        ErasedRule(Box::new(WithMessage {
+            inner: self,
+            message,
+        }))
+    }
 }
 
 impl<I, M> Clone for ErasedRule<I, M> {
@@ -37,12 +82,34 @@ impl<I, M> Clone for ErasedRule<I, M> {
     }
 }
 
-pub trait BoxedRule<I, M> {
+/// erased rule is shared by reference between validations, so it should be `Send + Sync`,
+/// and every calling runs on a fresh copy of the rule.
+pub trait BoxedRule<I, M>: Send + Sync {
     fn clone_box(&self) -> Box<dyn BoxedRule<I, M>>;
 
-    fn call(&mut self, data: &mut I) -> Result<(), M>;
+    fn call(&self, data: &mut I) -> Result<(), M>;
 
     fn name(&self) -> &'static str;
+
+    /// every failed rule reports itself message, combinators report the messages of inner rules
+    fn call_many(&self, data: &mut I) -> Result<(), Vec<Failed<M>>> {
+        self.call(data)
+            .map_err(|msg| vec![(self.name(), Vec::new(), msg)])
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        vec![self.name()]
+    }
+
+    fn info(&self) -> RuleInfo {
+        RuleInfo::new(self.name())
+    }
+
+    /// only async rule returns future
+    #[cfg(feature = "async")]
+    fn call_async<'a>(&'a self, _data: &'a mut I) -> Option<BoxFuture<'a, Result<(), M>>> {
+        None
+    }
 }
 
 pub struct RuleIntoBoxed<H, M, T> {
@@ -84,13 +151,19 @@ where
         Box::new(self.clone())
     }
 
-    fn call(&mut self, data: &mut I) -> Result<(), M> {
-        self.handler.call(data)
+    fn call(&self, data: &mut I) -> Result<(), M> {
+        self.handler.clone().call(data)
     }
 
     fn name(&self) -> &'static str {
         H::THE_NAME
     }
+
+    fn info(&self) -> RuleInfo {
+        RuleInfo::new(H::THE_NAME)
+            .with_params(self.handler.params())
+            .custom(H::IS_CUSTOM)
+    }
 }
 
 pub struct Map<I, M, M2> {
@@ -117,10 +190,125 @@ where
         Box::new(self.clone())
     }
 
-    fn call(&mut self, data: &mut I) -> Result<(), M2> {
+    fn call(&self, data: &mut I) -> Result<(), M2> {
         self.inner.call(data).map_err(self.layer)
     }
 
+    fn call_many(&self, data: &mut I) -> Result<(), Vec<Failed<M2>>> {
+        self.inner.call_many(data).map_err(|list| {
+            list.into_iter()
+                .map(|(name, path, msg)| (name, path, (self.layer)(msg)))
+                .collect()
+        })
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.inner.names()
+    }
+
+    fn info(&self) -> RuleInfo {
+        self.inner.info()
+    }
+
+    #[cfg(feature = "async")]
+    fn call_async<'a>(&'a self, data: &'a mut I) -> Option<BoxFuture<'a, Result<(), M2>>> {
+        let layer = self.layer;
+        self.inner.call_async(data).map(|fut| {
+            Box::pin(async move { fut.await.map_err(layer) }) as BoxFuture<'a, Result<(), M2>>
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+/// rename the rule, it is used by closures, their default name is `custom`
+pub struct Named<I, M> {
+    inner: ErasedRule<I, M>,
+    name: &'static str,
+}
+
+impl<I, M> Clone for Named<I, M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            name: self.name,
+        }
+    }
+}
+
+impl<I, M> BoxedRule<I, M> for Named<I, M>
+where
+    M: 'static,
+    I: 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<I, M>> {
+        Box::new(self.clone())
+    }
+
+    fn call(&self, data: &mut I) -> Result<(), M> {
+        self.inner.call(data)
+    }
+
+    #[cfg(feature = "async")]
+    fn call_async<'a>(&'a self, data: &'a mut I) -> Option<BoxFuture<'a, Result<(), M>>> {
+        self.inner.call_async(data)
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn info(&self) -> RuleInfo {
+        self.inner.info().with_name(self.name)
+    }
+}
+
+/// override the message of the rule, the messages of inner rules are overridden as well
+pub struct WithMessage<I, M> {
+    inner: ErasedRule<I, M>,
+    message: M,
+}
+
+impl<I, M: Clone> Clone for WithMessage<I, M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl<I, M> BoxedRule<I, M> for WithMessage<I, M>
+where
+    M: Clone + Send + Sync + 'static,
+    I: 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<I, M>> {
+        Box::new(self.clone())
+    }
+
+    fn call(&self, data: &mut I) -> Result<(), M> {
+        self.inner.call(data).map_err(|_| self.message.clone())
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.inner.names()
+    }
+
+    fn info(&self) -> RuleInfo {
+        self.inner.info()
+    }
+
+    #[cfg(feature = "async")]
+    fn call_async<'a>(&'a self, data: &'a mut I) -> Option<BoxFuture<'a, Result<(), M>>> {
+        self.inner.call_async(data).map(|fut| {
+            Box::pin(async move { fut.await.map_err(|_| self.message.clone()) })
+                as BoxFuture<'a, Result<(), M>>
+        })
+    }
+
     fn name(&self) -> &'static str {
         self.inner.name()
     }
@@ -0,0 +1,499 @@
+//! boolean combinators of rules, they compose rules and closures into one rule.
+//!
+//! - [`any_of`] or [`RuleList::or`]: passes when any rule passes, else all failed messages are reported.
+//! - [`all_of`]: passes when all rules pass, else all failed messages are reported, it is useful in `any_of` and `not`.
+//! - [`xor`]: passes when exactly one rule passes, the message is reported when more than one rule passes.
+//! - [`not`]: passes when the rules fail, the message is reported when all of them pass.
+//!
+//! the messages of inner rules are reported by their names, e.g. `contact.email`,
+//! giving the combinator a name by [`RuleList::named`] will report the first message under the name.
+//!
+//! the built-in rules fail on a missing field, so `not` passes on it, chain `Required` to report the field.
+//! closures are skipped on a missing field, so `not` of closures fails.
+//!
+//! # Panic
+//!
+//! - the combinator has not any rule, e.g. `any_of(RuleList::default())`
+//! - the combinator composes async rules, they are not supported in combinators
+//!
+//! # Example
+//! ```rust
+//! # #[cfg(feature = "full")]
+//! # {
+//! # use serde::Serialize;
+//! # use valitron::{
+//! #     available::{Email, Length, Message, MessageKind, Regex, StartWith},
+//! #     not, RuleExt, Validator,
+//! # };
+//! #[derive(Serialize)]
+//! struct User {
+//!     username: &'static str,
+//!     contact: &'static str,
+//! }
+//!
+//! let validator = Validator::new()
+//!     .rule(
+//!         "username",
+//!         not(
+//!             StartWith("admin").and(Length(..6)),
+//!             Message::fallback("the username is reserved"),
+//!         ),
+//!     )
+//!     .rule("contact", Email.or(Regex::new(r"^\+[1-9]\d{1,14}$")));
+//!
+//! let user = User {
+//!     username: "admin",
+//!     contact: "+8613800000000",
+//! };
+//! let err = validator.validate(user).unwrap_err();
+//! assert_eq!(err.len(), 1);
+//! assert_eq!(err.get("username").unwrap()[0].to_string(), "the username is reserved");
+//!
+//! let user = User {
+//!     username: "admin_foo",
+//!     contact: "foo",
+//! };
+//! let err = validator.validate(user).unwrap_err();
+//! assert_eq!(
+//!     err.get("contact").unwrap(),
+//!     &vec![Message::new(MessageKind::Email), Message::new(MessageKind::Regex)]
+//! );
+//! # }
+//! ```
+
+use super::{
+    boxed::{BoxedRule, ErasedRule, Failed},
+    IntoRuleList, RuleInfo, RuleList,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    AnyOf,
+    AllOf,
+    Xor,
+    Not,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::AnyOf => "any_of",
+            Kind::AllOf => "all_of",
+            Kind::Xor => "xor",
+            Kind::Not => "not",
+        }
+    }
+}
+
+struct Combinator<I, M> {
+    kind: Kind,
+    rules: Vec<ErasedRule<I, M>>,
+    is_bail: bool,
+    /// it is used by `xor` and `not`, when the inner rules pass
+    message: Option<M>,
+}
+
+impl<I, M: Clone> Clone for Combinator<I, M> {
+    fn clone(&self) -> Self {
+        Self {
+            kind: self.kind,
+            rules: self.rules.clone(),
+            is_bail: self.is_bail,
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl<I, M> BoxedRule<I, M> for Combinator<I, M>
+where
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    fn clone_box(&self) -> Box<dyn BoxedRule<I, M>> {
+        Box::new(self.clone())
+    }
+
+    fn call(&self, data: &mut I) -> Result<(), M> {
+        self.call_many(data)
+            .map_err(|list| list.into_iter().map(|(_, _, msg)| msg).next().unwrap())
+    }
+
+    fn call_many(&self, data: &mut I) -> Result<(), Vec<Failed<M>>> {
+        let mut failed = Vec::new();
+        let mut passed = 0;
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            match rule.call_many(data) {
+                Ok(()) => passed += 1,
+                Err(list) => failed.extend(list.into_iter().map(|(name, mut path, msg)| {
+                    path.insert(0, index);
+                    (name, path, msg)
+                })),
+            }
+
+            match self.kind {
+                Kind::AnyOf if passed > 0 => return Ok(()),
+                Kind::AllOf if self.is_bail && !failed.is_empty() => break,
+                _ => (),
+            }
+        }
+
+        let own_message = || {
+            let msg = self
+                .message
+                .clone()
+                .expect("combinator message is not found");
+            Err(vec![(self.kind.name(), Vec::new(), msg)])
+        };
+
+        match self.kind {
+            Kind::AnyOf | Kind::AllOf if failed.is_empty() => Ok(()),
+            Kind::AnyOf | Kind::AllOf => Err(failed),
+            Kind::Xor if passed == 1 => Ok(()),
+            Kind::Xor if passed == 0 && !failed.is_empty() => Err(failed),
+            Kind::Xor => own_message(),
+            Kind::Not if failed.is_empty() => own_message(),
+            Kind::Not => Ok(()),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    fn info(&self) -> RuleInfo {
+        RuleInfo::new(self.kind.name())
+            .with_inner(self.rules.iter().map(ErasedRule::info).collect())
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        let mut names = vec![self.kind.name()];
+        names.extend(self.rules.iter().flat_map(ErasedRule::names));
+        names
+    }
+}
+
+fn combine<I, M>(kind: Kind, list: RuleList<I, M>, message: Option<M>) -> RuleList<I, M>
+where
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    assert!(
+        !list.is_empty(),
+        "`{}` needs one rule at least",
+        kind.name()
+    );
+    assert!(
+        !list.list.iter().any(|rule| rule.info().is_async()),
+        "async rule can not be composed by `{}`",
+        kind.name()
+    );
+
+    let is_bail = list.is_bail();
+    RuleList {
+        list: vec![ErasedRule(Box::new(Combinator {
+            kind,
+            rules: list.list,
+            is_bail,
+            message,
+        }))],
+        ..Default::default()
+    }
+}
+
+/// passes when any of the rules passes, or else the messages of all rules are reported
+pub fn any_of<R, I, M>(rules: R) -> RuleList<I, M>
+where
+    R: IntoRuleList<I, M>,
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    combine(Kind::AnyOf, rules.into_list(), None)
+}
+
+/// passes when all of the rules pass, or else the messages of failed rules are reported,
+/// when the rules are bail, only the first message is reported.
+pub fn all_of<R, I, M>(rules: R) -> RuleList<I, M>
+where
+    R: IntoRuleList<I, M>,
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    combine(Kind::AllOf, rules.into_list(), None)
+}
+
+/// passes when exactly one of the rules passes.
+///
+/// when none of them passes, the messages of all rules are reported,
+/// when more than one passes, the message is reported.
+pub fn xor<R, I, M>(rules: R, message: M) -> RuleList<I, M>
+where
+    R: IntoRuleList<I, M>,
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    combine(Kind::Xor, rules.into_list(), Some(message))
+}
+
+/// passes when the rules fail, e.g. `not(StartWith("admin").and(Length(..6)), msg)`,
+/// when all of the rules pass, the message is reported.
+///
+/// it is different from [`Not`], which is reversing single rule with the message of the rule.
+///
+/// [`Not`]: crate::available::Not
+pub fn not<R, I, M>(rules: R, message: M) -> RuleList<I, M>
+where
+    R: IntoRuleList<I, M>,
+    I: 'static,
+    M: Clone + Send + Sync + 'static,
+{
+    combine(Kind::Not, rules.into_list(), Some(message))
+}
+
+impl<I, M> RuleList<I, M> {
+    /// passes when the rules or other rules pass, e.g. `Required.and(Email).or(Regex::new(..))`
+    /// is `(Required and Email) or Regex`, the messages of both are reported when they fail.
+    ///
+    /// the bail, each and nullable setting of self are kept.
+    pub fn or<R>(self, other: R) -> Self
+    where
+        R: IntoRuleList<I, M>,
+        I: 'static,
+        M: Clone + Send + Sync + 'static,
+    {
+        fn operand<I, M>(list: RuleList<I, M>) -> ErasedRule<I, M>
+        where
+            I: 'static,
+            M: Clone + Send + Sync + 'static,
+        {
+            if list.len() == 1 {
+                list.list.into_iter().next().unwrap()
+            } else {
+                combine(Kind::AllOf, list, None).list.remove(0)
+            }
+        }
+
+        let RuleList {
+            list,
+            is_bail,
+            is_each,
+            is_nullable,
+        } = self;
+        let left = RuleList {
+            list,
+            is_bail,
+            ..Default::default()
+        };
+
+        let rules = RuleList {
+            list: vec![operand(left), operand(other.into_list())],
+            ..Default::default()
+        };
+
+        RuleList {
+            is_bail,
+            is_each,
+            is_nullable,
+            ..combine(Kind::AnyOf, rules, None)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        available::{Email, Length, Message, MessageKind, Required, StartWith},
+        custom, RuleExt, Validator,
+    };
+
+    #[derive(Serialize)]
+    struct Input {
+        name: &'static str,
+    }
+
+    fn input(name: &'static str) -> Input {
+        Input { name }
+    }
+
+    fn not_foo(name: &mut String) -> Result<(), Message> {
+        if name == "foo" {
+            Err("the name is foo".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_any_of() {
+        let validator = Validator::new().rule(
+            "name",
+            any_of(StartWith("a").and(StartWith("b")).custom(not_foo)),
+        );
+
+        validator.validate(input("bar")).unwrap();
+
+        let err = validator.validate(input("foo")).unwrap_err();
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::new(MessageKind::StartWith("a".into())),
+                Message::new(MessageKind::StartWith("b".into())),
+                Message::from("the name is foo"),
+            ]
+        );
+
+        // message of inner rule is customizable, and the instance of inner rule is the first one
+        let err = validator
+            .clone()
+            .message([("name.start_with", "bad prefix"), ("name.custom", "is foo")])
+            .validate(input("foo"))
+            .unwrap_err();
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::from("bad prefix"),
+                Message::from("bad prefix"),
+                Message::from("is foo"),
+            ]
+        );
+
+        // the first message is reported by bail
+        let err = validator.bail().validate(input("foo")).unwrap_err();
+        assert_eq!(err.total(), 1);
+    }
+
+    #[test]
+    fn test_or() {
+        let validator = Validator::new().rule(
+            "name",
+            Required.and(StartWith("a")).or(Email).and(Length(..=20)),
+        );
+
+        validator.validate(input("abc")).unwrap();
+        validator.validate(input("foo@example.com")).unwrap();
+
+        let err = validator.validate(input("foo")).unwrap_err();
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::new(MessageKind::StartWith("a".into())),
+                Message::new(MessageKind::Email),
+            ]
+        );
+
+        let err = validator
+            .validate(input("abcdefghijklmnopqrstuvwxyz"))
+            .unwrap_err();
+        assert_eq!(err["name"], vec![Message::new(MessageKind::Length)]);
+
+        // the combinator is renamed, the first message is reported under the name
+        let err = Validator::new()
+            .rule("name", StartWith("a").or(Email).named("contact"))
+            .message([("name.contact", "invalid contact")])
+            .validate(input("foo"))
+            .unwrap_err();
+        assert_eq!(err["name"], vec![Message::from("invalid contact")]);
+    }
+
+    #[test]
+    fn test_all_of() {
+        let validator = Validator::new().rule(
+            "name",
+            all_of(StartWith("a").and(StartWith("b")).bail()).or(Email),
+        );
+        let err = validator.validate(input("foo")).unwrap_err();
+        assert_eq!(
+            err["name"],
+            vec![
+                Message::new(MessageKind::StartWith("a".into())),
+                Message::new(MessageKind::Email),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_xor() {
+        let validator = Validator::new().rule(
+            "name",
+            xor(
+                StartWith("a").and(StartWith("ab")),
+                Message::fallback("only one prefix"),
+            ),
+        );
+
+        validator.validate(input("ac")).unwrap();
+
+        let err = validator.validate(input("abc")).unwrap_err();
+        assert_eq!(err["name"], vec![Message::from("only one prefix")]);
+
+        let err = validator.validate(input("foo")).unwrap_err();
+        assert_eq!(err["name"].len(), 2);
+    }
+
+    #[test]
+    fn test_not() {
+        let validator = Validator::new()
+            .rule(
+                "name",
+                not(
+                    StartWith("admin").and(Length(..6)),
+                    Message::fallback("reserved"),
+                ),
+            )
+            .rule(
+                "other",
+                not(custom(not_foo), Message::fallback("should be foo")),
+            );
+
+        #[derive(Serialize)]
+        struct Pair {
+            name: &'static str,
+            other: &'static str,
+        }
+
+        validator
+            .validate(Pair {
+                name: "administrator",
+                other: "foo",
+            })
+            .unwrap();
+
+        let err = validator
+            .validate(Pair {
+                name: "admin",
+                other: "bar",
+            })
+            .unwrap_err();
+        assert_eq!(err["name"], vec![Message::from("reserved")]);
+        assert_eq!(err["other"], vec![Message::from("should be foo")]);
+
+        // the names of inner rules are registered
+        let validator = validator
+            .message([("name.not", "is reserved"), ("name.length", "too short")])
+            .message([("other.custom", "the custom")]);
+        let err = validator
+            .validate(Pair {
+                name: "admin",
+                other: "bar",
+            })
+            .unwrap_err();
+        assert_eq!(err["name"], vec![Message::from("is reserved")]);
+        // `not` fails with its own message, so the messages of inner rules are not used
+        assert_eq!(err["other"], vec![Message::from("should be foo")]);
+
+        // the built-in rules fail on a missing field, so `not` passes,
+        // but a closure is skipped, so `not` fails
+        let err = validator.validate(input("administrator")).unwrap_err();
+        assert!(!err.contains_key("name"));
+        assert_eq!(err["other"], vec![Message::from("should be foo")]);
+    }
+
+    #[test]
+    #[should_panic = "`any_of` needs one rule at least"]
+    fn test_empty() {
+        let _ = Validator::<Message>::new().rule("name", any_of(RuleList::default()));
+    }
+}
@@ -0,0 +1,191 @@
+//! description of registered rules, it is used to generate docs and client-side hints
+
+#[cfg(feature = "full")]
+use std::ops::{Bound, RangeBounds};
+
+use crate::Value;
+
+/// description of a registered rule, it is returned by [`Validator::fields`]
+///
+/// [`Validator::fields`]: crate::register::InnerValidator::fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleInfo {
+    name: &'static str,
+    params: Vec<(&'static str, Value)>,
+    inner: Vec<RuleInfo>,
+    is_custom: bool,
+    is_async: bool,
+}
+
+impl RuleInfo {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            params: Vec::new(),
+            inner: Vec::new(),
+            is_custom: false,
+            is_async: false,
+        }
+    }
+
+    pub(crate) fn with_params(mut self, params: Vec<(&'static str, Value)>) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub(crate) fn with_inner(mut self, inner: Vec<RuleInfo>) -> Self {
+        self.inner = inner;
+        self
+    }
+
+    pub(crate) fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub(crate) fn custom(mut self, is_custom: bool) -> Self {
+        self.is_custom = is_custom;
+        self
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn asynchronous(mut self) -> Self {
+        self.is_async = true;
+        self
+    }
+
+    /// the name of rule, it is used by message key, e.g. `length`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// parameters of the rule, e.g. `min` and `max` of `Length`, see [`Rule::params`]
+    ///
+    /// [`Rule::params`]: crate::Rule::params
+    pub fn params(&self) -> &[(&'static str, Value)] {
+        &self.params
+    }
+
+    /// get a parameter by its name
+    pub fn param(&self, name: &str) -> Option<&Value> {
+        self.params
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// the rules composed by combinators, e.g. `any_of`, it is empty for other rules
+    pub fn inner(&self) -> &[RuleInfo] {
+        &self.inner
+    }
+
+    /// the rule is a closure, its logic can not be described
+    pub fn is_custom(&self) -> bool {
+        self.is_custom
+    }
+
+    /// the rule is async rule
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+}
+
+/// the bound of range, it is converted to parameter value
+#[cfg(feature = "full")]
+pub(crate) trait BoundParam: Clone {
+    fn into_param(self) -> Value;
+
+    /// the next value of integer, it turns exclusive bound into inclusive
+    fn next(&self) -> Option<Self> {
+        None
+    }
+
+    fn prev(&self) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(feature = "full")]
+macro_rules! impl_bound_param {
+    ($($val:ident($ty:ty => $as:ty)),*) => {
+        $(
+            impl BoundParam for $ty {
+                fn into_param(self) -> Value {
+                    Value::$val(self as $as)
+                }
+
+                fn next(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn prev(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "full")]
+impl_bound_param!(
+    Uint8(u8 => u8),
+    Int8(i8 => i8),
+    Uint16(u16 => u16),
+    Int16(i16 => i16),
+    Uint32(u32 => u32),
+    Int32(i32 => i32),
+    Uint64(u64 => u64),
+    Int64(i64 => i64),
+    Uint64(usize => u64)
+);
+
+#[cfg(feature = "full")]
+impl BoundParam for char {
+    fn into_param(self) -> Value {
+        Value::Char(self)
+    }
+}
+
+#[cfg(feature = "full")]
+impl BoundParam for f32 {
+    fn into_param(self) -> Value {
+        Value::Float32(self.into())
+    }
+}
+
+#[cfg(feature = "full")]
+impl BoundParam for f64 {
+    fn into_param(self) -> Value {
+        Value::Float64(self.into())
+    }
+}
+
+/// `min` and `max` are inclusive, the exclusive bound of integer is turned into inclusive,
+/// others are `exclusive_min` and `exclusive_max`, e.g. `0.0..1.0`
+#[cfg(feature = "full")]
+pub(crate) fn range_params<T, R>(range: &R) -> Vec<(&'static str, Value)>
+where
+    T: BoundParam,
+    R: RangeBounds<T>,
+{
+    let mut params = Vec::new();
+
+    match range.start_bound() {
+        Bound::Included(n) => params.push(("min", n.clone().into_param())),
+        Bound::Excluded(n) => match n.next() {
+            Some(next) => params.push(("min", next.into_param())),
+            None => params.push(("exclusive_min", n.clone().into_param())),
+        },
+        Bound::Unbounded => (),
+    }
+    match range.end_bound() {
+        Bound::Included(n) => params.push(("max", n.clone().into_param())),
+        Bound::Excluded(n) => match n.prev() {
+            Some(prev) => params.push(("max", prev.into_param())),
+            None => params.push(("exclusive_max", n.clone().into_param())),
+        },
+        Bound::Unbounded => (),
+    }
+
+    params
+}
@@ -20,20 +20,36 @@
 //! }
 //! ```
 
-use std::{collections::HashMap, fmt::Display, slice::Iter};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    slice::Iter,
+};
 
 use crate::{
-    register::IntoMessage,
+    register::{IntoMessage, MessageInfo},
     value::{FromValue, Value, ValueMap},
 };
 
-use self::boxed::{ErasedRule, RuleIntoBoxed};
+use self::boxed::{ErasedRule, Failed, RuleIntoBoxed};
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::{async_rule, AsyncRule, AsyncRuleExt};
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
 #[cfg(feature = "full")]
 pub mod available;
 mod boxed;
+pub mod combinator;
+mod info;
 pub mod string;
 
+pub use self::info::RuleInfo;
+#[cfg(feature = "full")]
+pub(crate) use self::info::{range_params, BoundParam};
+
 #[cfg(test)]
 mod test;
 
@@ -59,7 +75,7 @@ mod test;
 ///     }
 /// }
 /// ```
-pub trait CoreRule<I, T>: 'static + Sized + Clone {
+pub trait CoreRule<I, T>: 'static + Sized + Clone + Send + Sync {
     /// custom define returning message type
     type Message;
 
@@ -68,11 +84,20 @@ pub trait CoreRule<I, T>: 'static + Sized + Clone {
     /// allow `a-z` | `A-Z` | `0-9` | `_` composed string, and not start with `0-9`
     const THE_NAME: &'static str;
 
+    /// the rule is a closure
+    #[doc(hidden)]
+    const IS_CUSTOM: bool = false;
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     ///
     /// success returning Ok(()), or else returning message.
     fn call(&mut self, data: &mut I) -> Result<(), Self::Message>;
 
+    /// parameters of the rule, see [`Rule::params`]
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+
     #[doc(hidden)]
     fn into_boxed(self) -> RuleIntoBoxed<Self, Self::Message, T> {
         RuleIntoBoxed::new(self)
@@ -101,6 +126,24 @@ pub trait RuleExt<Input, Msg>: private::Sealed<Input> {
         F: for<'a> FnOnce(&'a mut V) -> Result<(), Msg>,
         F: CoreRule<Input, V, Message = Msg>,
         V: FromValue + 'static;
+
+    /// sometimes/nullable mode, see [`RuleList::nullable`]
+    fn nullable(self) -> RuleList<Input, Msg>;
+
+    /// passes when any of them passes, see [`combinator`]
+    fn or<R>(self, other: R) -> RuleList<Input, Msg>
+    where
+        R: CoreRule<Input, (), Message = Msg>,
+        Input: 'static,
+        Msg: Clone + Send + Sync;
+
+    /// override the default message of the rule, e.g. `Required.with_message("title is required")`,
+    /// the rules appended later keep their own messages, see [`RuleList::with_message`]
+    fn with_message<T>(self, message: T) -> RuleList<Input, Msg>
+    where
+        T: Into<Msg>,
+        Input: 'static,
+        Msg: Clone + Send + Sync;
 }
 
 impl<R, Input, Msg> RuleExt<Input, Msg> for R
@@ -112,19 +155,8 @@ where
     where
         R2: CoreRule<Input, (), Message = Msg>,
     {
-        let is_dup = {
-            if R::THE_NAME != R2::THE_NAME {
-                false
-            } else {
-                !matches!(R::THE_NAME, "custom")
-            }
-        };
         RuleList {
-            list: if is_dup {
-                vec![ErasedRule::new(self)]
-            } else {
-                vec![ErasedRule::<Input, Msg>::new(self), ErasedRule::new(other)]
-            },
+            list: vec![ErasedRule::<Input, Msg>::new(self), ErasedRule::new(other)],
             ..Default::default()
         }
     }
@@ -140,12 +172,43 @@ where
             ..Default::default()
         }
     }
+
+    fn nullable(self) -> RuleList<Input, Msg> {
+        RuleList {
+            list: vec![ErasedRule::new(self)],
+            is_nullable: true,
+            ..Default::default()
+        }
+    }
+
+    fn or<R2>(self, other: R2) -> RuleList<Input, Msg>
+    where
+        R2: CoreRule<Input, (), Message = Msg>,
+        Input: 'static,
+        Msg: Clone + Send + Sync,
+    {
+        combinator::any_of(self.and(other))
+    }
+
+    fn with_message<T>(self, message: T) -> RuleList<Input, Msg>
+    where
+        T: Into<Msg>,
+        Input: 'static,
+        Msg: Clone + Send + Sync,
+    {
+        RuleList {
+            list: vec![ErasedRule::new(self).with_message(message.into())],
+            ..Default::default()
+        }
+    }
 }
 
 /// Rules collection
 pub struct RuleList<I, M> {
     pub(crate) list: Vec<ErasedRule<I, M>>,
     is_bail: bool,
+    is_each: bool,
+    is_nullable: bool,
 }
 
 impl<I, M> Default for RuleList<I, M> {
@@ -153,6 +216,8 @@ impl<I, M> Default for RuleList<I, M> {
         Self {
             list: Vec::new(),
             is_bail: false,
+            is_each: false,
+            is_nullable: false,
         }
     }
 }
@@ -162,11 +227,28 @@ impl<I, M> Clone for RuleList<I, M> {
         Self {
             list: self.list.clone(),
             is_bail: self.is_bail,
+            is_each: self.is_each,
+            is_nullable: self.is_nullable,
         }
     }
 }
 
+impl<I, M> Debug for RuleList<I, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleList")
+            .field("list", &self.info())
+            .field("is_bail", &self.is_bail)
+            .field("is_each", &self.is_each)
+            .field("is_nullable", &self.is_nullable)
+            .finish()
+    }
+}
+
 impl<I, M> RuleList<I, M> {
+    #[deprecated(
+        since = "0.5.4",
+        note = "the rules with the same name are kept as instances, e.g. `start_with#1`"
+    )]
     pub fn remove_duplicate(&mut self, other: &ErasedRule<I, M>) {
         let name = other.name();
 
@@ -185,22 +267,20 @@ impl<I, M> RuleList<I, M> {
             .collect();
 
         for index in duplicate_rules {
-            // Use `swap_remove` to get better performence because we don't
-            // mind the order of rule list. If the order should be kept in
-            // the future, please use `remove` instead of `swap_remove`.
-            self.list.swap_remove(index);
+            // Use `remove` to keep the order of rule list, rules are executed
+            // and messages are reported in declared order.
+            self.list.remove(index);
         }
     }
 
+    /// append a rule, the rule with the same name is kept as another instance,
+    /// e.g. `StartWith("a").and(StartWith("b"))`, its message key is `start_with#1`
     pub fn and<R>(mut self, other: R) -> Self
     where
         R: CoreRule<I, (), Message = M>,
         M: 'static,
     {
-        let other = ErasedRule::new(other);
-        self.remove_duplicate(&other);
-
-        self.list.push(other);
+        self.list.push(ErasedRule::new(other));
         self
     }
 
@@ -215,6 +295,69 @@ impl<I, M> RuleList<I, M> {
         self
     }
 
+    /// give the last rule a name, it is used by closures, so every closure has its own message key,
+    /// e.g. `custom(f).named("age_range")` is `age.age_range` instead of `age.custom`.
+    ///
+    /// the name should be composed by `a-z` | `A-Z` | `0-9` | `_`, and not start with `0-9`
+    ///
+    /// # Panic
+    ///
+    /// - the last rule is a built-in rule, only closures and combinators can be named
+    pub fn named(mut self, name: &'static str) -> Self
+    where
+        I: 'static,
+        M: 'static,
+    {
+        if let Some(rule) = self.list.pop() {
+            let info = rule.info();
+            assert!(
+                info.is_custom() || !info.inner().is_empty(),
+                "the built-in rule `{}` can not be named `{name}`",
+                info.name()
+            );
+            self.list.push(rule.named(name));
+        }
+        self
+    }
+
+    /// override the messages of all rules in the list, e.g. `Required.and(Email).with_message("invalid email")`
+    /// reports `invalid email` when `Required` or `Email` fails, it survives [`Validator::map`].
+    ///
+    /// the rules with different messages are registered to the same field one by one,
+    /// e.g. `.rule("email", Required.with_message(..)).rule("email", Email.with_message(..))`.
+    ///
+    /// the message registered by [`Validator::message`] takes precedence over it.
+    ///
+    /// [`Validator::map`]: crate::Validator::map
+    /// [`Validator::message`]: crate::Validator::message
+    pub fn with_message<T>(mut self, message: T) -> Self
+    where
+        T: Into<M>,
+        I: 'static,
+        M: Clone + Send + Sync + 'static,
+    {
+        let message = message.into();
+        self.list = self
+            .list
+            .into_iter()
+            .map(|rule| rule.with_message(message.clone()))
+            .collect();
+        self
+    }
+
+    /// append closure rule with external context, see [`custom_with_context`]
+    pub fn custom_with_context<F, V, C>(mut self, other: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a mut V, &'a C) -> Result<(), M>,
+        F: CoreRule<I, (V, C), Message = M>,
+        V: FromValue + 'static,
+        C: Any + Send + Sync,
+        M: 'static,
+    {
+        self.list.push(ErasedRule::new(other));
+        self
+    }
+
     /// when first validate error is encountered, right away return Err(message) in one field.
     ///
     /// when [`Validator`] set bail, it will cover, and comply with [`Validator`]
@@ -233,6 +376,35 @@ impl<I, M> RuleList<I, M> {
         self.is_bail
     }
 
+    /// the rules are applied to every item of the field, it should be array, tuple or map,
+    /// and error messages are reported by concrete index, e.g. `tags[0]`, `tags[1]` ...
+    ///
+    /// it is same as registering the field with `[*]`, e.g. `tags[*]`
+    pub fn each(mut self) -> Self {
+        self.is_each = true;
+        self
+    }
+
+    /// sometimes/nullable mode, the rules are skipped when the field value is `None`,
+    /// or the field is absent because of `None` parent, e.g. `profile.avatar.url` with `avatar: None`.
+    ///
+    /// otherwise, `None` value is validated by rules, e.g. [`Required`] fails on it.
+    ///
+    /// [`Required`]: crate::available::Required
+    pub fn nullable(mut self) -> Self {
+        self.is_nullable = true;
+        self
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.is_nullable
+    }
+
+    /// it is used by registering, the flag is consumed
+    pub(crate) fn take_each(&mut self) -> bool {
+        std::mem::take(&mut self.is_each)
+    }
+
     pub fn len(&self) -> usize {
         self.list.len()
     }
@@ -241,42 +413,73 @@ impl<I, M> RuleList<I, M> {
         self.list.is_empty()
     }
 
-    pub(crate) fn merge(&mut self, other: &mut RuleList<I, M>) {
-        for new_rule in &other.list {
-            self.remove_duplicate(new_rule);
-        }
+    /// description of the rules in the declared order
+    pub fn info(&self) -> Vec<RuleInfo> {
+        self.list.iter().map(ErasedRule::info).collect()
+    }
 
+    /// append the rules of other, the settings of other are ignored
+    #[cfg(feature = "full")]
+    pub(crate) fn extend(&mut self, other: RuleList<I, M>) {
+        self.list.extend(other.list);
+    }
+
+    /// append the rules of other, the rule with the same name is kept as another instance
+    pub(crate) fn merge(&mut self, other: &mut RuleList<I, M>) {
         self.list.append(&mut other.list);
         self.is_bail = self.is_bail || other.is_bail;
+        self.is_nullable = self.is_nullable || other.is_nullable;
     }
 
     fn iter(&self) -> Iter<'_, ErasedRule<I, M>> {
         self.list.iter()
     }
 
-    /// check the rule name is existing
+    /// check the rule name is existing, the instance of rule is also supported, e.g. `start_with#1`
+    ///
+    /// inner rules of combinators are regarded as the first instance, same as `instance_of`
     pub(crate) fn contains(&self, rule: &str) -> bool {
-        self.iter().map(ErasedRule::name).any(|name| name == rule)
+        match rule.split_once('#') {
+            Some((rule, instance)) => instance.parse::<usize>().is_ok_and(|instance| {
+                if instance == 0 {
+                    return self.contains(rule);
+                }
+                self.iter().filter(|item| item.name() == rule).count() > instance
+            }),
+            None => self
+                .iter()
+                .flat_map(ErasedRule::names)
+                .any(|name| name == rule),
+        }
     }
 
-    /// check all rule names is valid or not
-    pub(crate) fn valid_name(&self) -> bool {
-        self.iter().map(ErasedRule::name).all(|name| {
+    /// the instance index of rule in the same name rules, it is `0` when the name is unique
+    fn instance(&self, index: usize) -> usize {
+        let name = self.list[index].name();
+        self.list[..index]
+            .iter()
+            .filter(|rule| rule.name() == name)
+            .count()
+    }
+
+    /// find the first invalid rule name
+    pub(crate) fn invalid_name(&self) -> Option<&'static str> {
+        self.iter().flat_map(ErasedRule::names).find(|name| {
             let mut chares = name.chars();
             let first = match chares.next() {
                 Some(ch) => ch,
-                None => return false,
+                None => return true,
             };
 
             if !(first.is_ascii_alphabetic() || first == '_') {
-                return false;
+                return true;
             }
 
             loop {
                 match chares.next() {
                     Some(ch) if ch.is_ascii_alphanumeric() || ch == '_' => (),
-                    None => break true,
-                    _ => break false,
+                    None => break false,
+                    _ => break true,
                 }
             }
         })
@@ -298,23 +501,50 @@ impl<I, M> RuleList<I, M> {
         RuleList {
             list,
             is_bail: self.is_bail,
+            is_each: self.is_each,
+            is_nullable: self.is_nullable,
         }
     }
 }
 
 impl<M> RuleList<ValueMap, M> {
+    /// call the rule of index, returns the failed rules, a combinator returns more than one.
+    fn call_failed(&self, index: usize, data: &mut ValueMap) -> Vec<Failed<M>> {
+        self.list[index].call_many(data).err().unwrap_or_default()
+    }
+
+    /// call the rule of index, returns the failed rule names, their instance indexes and messages
+    fn call_endpoint(&self, index: usize, data: &mut ValueMap) -> Vec<(&'static str, usize, M)> {
+        self.call_failed(index, data)
+            .into_iter()
+            .map(|(name, path, msg)| (name, self.instance_of(index, &path), msg))
+            .collect()
+    }
+
+    /// inner rules of combinator are regarded as the first instance
+    fn instance_of(&self, index: usize, path: &[usize]) -> usize {
+        if path.is_empty() {
+            self.instance(index)
+        } else {
+            0
+        }
+    }
+
+    /// returns the failed rule name, its instance index and message
     #[must_use]
-    pub(crate) fn call(self, data: &mut ValueMap) -> Vec<(&'static str, M)> {
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+    pub(crate) fn call(&self, data: &mut ValueMap) -> Vec<(&'static str, usize, M)> {
+        let mut msg = Vec::with_capacity(self.list.len());
 
-        for endpoint in list.iter_mut() {
-            let _ = endpoint
-                .call(data)
-                .map_err(|e| msg.push((endpoint.name(), e)));
+        for index in 0..self.list.len() {
+            msg.extend(self.call_endpoint(index, data));
 
-            if self.is_bail && !msg.is_empty() {
-                msg.shrink_to(1);
+            if data.is_excluded() {
+                break;
+            }
+
+            // a missing field only reports the first message
+            if (self.is_bail || data.current().is_none()) && !msg.is_empty() {
+                msg.truncate(1);
                 return msg;
             }
         }
@@ -323,22 +553,35 @@ impl<M> RuleList<ValueMap, M> {
         msg
     }
 
+    /// parameters of the failed rule, the inner rule of combinator is found by its indexes
+    fn params_of(&self, index: usize, path: &[usize]) -> Vec<(&'static str, Value)> {
+        let info = self.list[index].info();
+        let info = path.iter().fold(&info, |info, &inner| &info.inner()[inner]);
+        info.params().to_vec()
+    }
+
     #[must_use]
-    pub(crate) fn call_gen_message<M2>(self, data: &mut ValueMap) -> Vec<M2>
+    pub(crate) fn call_gen_message<M2>(&self, data: &mut ValueMap, label: Option<&str>) -> Vec<M2>
     where
         M2: IntoMessage,
     {
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+        let mut msg = Vec::with_capacity(self.list.len());
+
+        for index in 0..self.list.len() {
+            for (name, path, _) in self.call_failed(index, data) {
+                let value = data.current().unwrap_or(&Value::Unit);
+                let params = self.params_of(index, &path);
+                let info = MessageInfo::new(name, data.as_index(), value, &params, label);
+                msg.push(M2::into_message_with(&info))
+            }
 
-        for endpoint in list.iter_mut() {
-            let _ = endpoint.call(data).map_err(|_| {
-                let value = data.current().unwrap();
-                msg.push(M2::into_message(endpoint.name(), data.as_index(), value))
-            });
+            if data.is_excluded() {
+                break;
+            }
 
-            if self.is_bail && !msg.is_empty() {
-                msg.shrink_to(1);
+            // a missing field only reports the first message
+            if (self.is_bail || data.current().is_none()) && !msg.is_empty() {
+                msg.truncate(1);
                 return msg;
             }
         }
@@ -348,32 +591,36 @@ impl<M> RuleList<ValueMap, M> {
     }
 
     pub(crate) fn call_string_message<'m>(
-        self,
+        &self,
         data: &mut ValueMap,
         message: &HashMap<&'m str, &'m str>,
+        label: Option<&str>,
     ) -> Vec<String>
     where
         M: Display,
     {
-        fn replace(s: &str, field: &str, value: &str) -> String {
-            let s = s.replace("{field}", field);
-            s.replace("{value}", value)
-        }
-
-        let RuleList { mut list, .. } = self;
-        let mut msg = Vec::with_capacity(list.len());
+        let mut msg = Vec::with_capacity(self.list.len());
 
-        for endpoint in list.iter_mut() {
-            let _ = endpoint.call(data).map_err(|def_msg| {
+        for index in 0..self.list.len() {
+            for (name, path, def_msg) in self.call_failed(index, data) {
                 let string = def_msg.to_string();
-                let mes = *(message.get(endpoint.name())).unwrap_or(&string.as_str());
-                let value = data.current().unwrap();
-                //let field = data.index;
-                msg.push(replace(mes, data.index.as_str(), &value.to_string()))
-            });
+                let instance = format!("{name}#{}", self.instance_of(index, &path));
+                let mes = *(message.get(instance.as_str()))
+                    .or_else(|| message.get(name))
+                    .unwrap_or(&string.as_str());
+                let value = data.current().unwrap_or(&Value::Unit);
+                let params = self.params_of(index, &path);
+                let info = MessageInfo::new(name, &data.index, value, &params, label);
+                msg.push(info.format(mes))
+            }
 
-            if self.is_bail && !msg.is_empty() {
-                msg.shrink_to(1);
+            if data.is_excluded() {
+                break;
+            }
+
+            // a missing field only reports the first message
+            if (self.is_bail || data.current().is_none()) && !msg.is_empty() {
+                msg.truncate(1);
                 return msg;
             }
         }
@@ -386,7 +633,7 @@ impl<M> RuleList<ValueMap, M> {
 impl<M> RuleList<String, M> {
     pub(crate) fn from_fn<F>(f: F) -> RuleList<String, M>
     where
-        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+        F: FnOnce(&mut String) -> Result<(), M> + Clone + Send + Sync + 'static,
         M: 'static,
     {
         RuleList {
@@ -398,7 +645,7 @@ impl<M> RuleList<String, M> {
     pub(crate) fn append_fn<S, F>(one: S, fun: F) -> RuleList<String, M>
     where
         S: CoreRule<String, (), Message = M>,
-        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+        F: FnOnce(&mut String) -> Result<(), M> + Clone + Send + Sync + 'static,
         M: 'static,
     {
         RuleList {
@@ -413,32 +660,20 @@ impl<M> RuleList<String, M> {
         S2: CoreRule<String, (), Message = M>,
         M: 'static,
     {
-        let is_dup = {
-            if S::THE_NAME != S2::THE_NAME {
-                false
-            } else {
-                !matches!(S::THE_NAME, "custom")
-            }
-        };
         RuleList {
-            list: if is_dup {
-                vec![ErasedRule::new(one)]
-            } else {
-                vec![ErasedRule::<String, M>::new(one), ErasedRule::new(two)]
-            },
+            list: vec![ErasedRule::<String, M>::new(one), ErasedRule::new(two)],
             ..Default::default()
         }
     }
 
     #[must_use]
-    pub(crate) fn call(self, data: &mut String) -> Vec<M> {
-        let RuleList { mut list, is_bail } = self;
-        let mut msg = Vec::with_capacity(list.len());
+    pub(crate) fn call(&self, data: &mut String) -> Vec<M> {
+        let mut msg = Vec::with_capacity(self.list.len());
 
-        for endpoint in list.iter_mut() {
+        for endpoint in self.iter() {
             let _ = endpoint.call(data).map_err(|m| msg.push(m));
 
-            if is_bail && !msg.is_empty() {
+            if self.is_bail && !msg.is_empty() {
                 msg.shrink_to(1);
                 return msg;
             }
@@ -467,6 +702,118 @@ where
     }
 }
 
+/// load closure rule with a name, it is same as `custom(f).named(name)`
+///
+/// ```rust
+/// # use valitron::{named, Validator};
+/// fn age_range(age: &mut u8) -> Result<(), &'static str> {
+///     if (18..=60).contains(age) {
+///         Ok(())
+///     } else {
+///         Err("age is out of range")
+///     }
+/// }
+///
+/// let validator = Validator::new()
+///     .rule("0", named("age_range", age_range))
+///     .message([("0.age_range", "age should be between 18 and 60")]);
+///
+/// let err = validator.validate((10_u8,)).unwrap_err();
+/// assert_eq!(err.get("0").unwrap()[0], "age should be between 18 and 60");
+/// ```
+pub fn named<F, V, Input, Msg>(name: &'static str, f: F) -> RuleList<Input, Msg>
+where
+    F: FnOnce(&mut V) -> Result<(), Msg>,
+    F: CoreRule<Input, V, Message = Msg>,
+    V: FromValue + 'static,
+    Input: 'static,
+    Msg: 'static,
+{
+    custom(f).named(name)
+}
+
+/// load closure rule with external context, the context is passed by [`validate_with_context`]
+///
+/// ```rust
+/// # use valitron::{custom_with_context, Validator};
+/// struct Tenant {
+///     max_seats: u8,
+/// }
+///
+/// fn seats_limit(seats: &mut u8, tenant: &Tenant) -> Result<(), String> {
+///     if *seats <= tenant.max_seats {
+///         Ok(())
+///     } else {
+///         Err(format!("the plan allows at most {} seats", tenant.max_seats))
+///     }
+/// }
+///
+/// let validator = Validator::<String>::new().rule("0", custom_with_context(seats_limit));
+///
+/// validator
+///     .validate_with_context((5_u8,), Tenant { max_seats: 10 })
+///     .unwrap();
+/// let err = validator
+///     .validate_with_context((15_u8,), Tenant { max_seats: 10 })
+///     .unwrap_err();
+/// assert_eq!(err.get("0").unwrap()[0], "the plan allows at most 10 seats");
+/// ```
+///
+/// when the context is not passed or its type is not matched, e.g. it is validated by `validate`,
+/// the closure fails with [`ClosureMessage::mismatch`].
+///
+/// [`validate_with_context`]: crate::register::Validator::validate_with_context
+pub fn custom_with_context<F, V, C, Input, Msg>(f: F) -> RuleList<Input, Msg>
+where
+    F: FnOnce(&mut V, &C) -> Result<(), Msg>,
+    F: CoreRule<Input, (V, C), Message = Msg>,
+    V: FromValue + 'static,
+    C: Any + Send + Sync,
+    Msg: 'static,
+{
+    RuleList {
+        list: vec![ErasedRule::new(f)],
+        ..Default::default()
+    }
+}
+
+/// wrap a closure to report the field that is not found and the value that is not the type of argument,
+/// with [`ClosureMessage::missing`] and [`ClosureMessage::mismatch`]
+///
+/// a plain closure is skipped when the field is not found, and panics when the type is not matched.
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use valitron::{checked, custom, ClosureMessage, Validator};
+/// fn is_adult(age: &mut u8) -> Result<(), String> {
+///     if *age >= 18 {
+///         Ok(())
+///     } else {
+///         Err("too young".to_string())
+///     }
+/// }
+///
+/// let validator = Validator::new().rule("age", custom(checked(is_adult)));
+///
+/// let err = validator
+///     .validate(HashMap::from([("name", 20_u8)]))
+///     .unwrap_err();
+/// assert_eq!(err.get("age").unwrap()[0], String::missing());
+/// ```
+pub fn checked<F, V, M>(
+    f: F,
+) -> impl FnOnce(&mut ValueMap) -> Result<(), M> + 'static + Clone + Send + Sync
+where
+    F: for<'a> FnOnce(&'a mut V) -> Result<(), M> + 'static + Clone + Send + Sync,
+    V: FromValue,
+    M: ClosureMessage,
+{
+    move |data: &mut ValueMap| match V::from_value(data) {
+        Some(val) => f(val),
+        None => Err(closure_error::<V, M>(data)),
+    }
+}
+
 impl<I, M> IntoRuleList<I, M> for RuleList<I, M> {
     fn into_list(self) -> Self {
         self
@@ -560,7 +907,7 @@ mod test_regster {
 }
 
 /// used by convenient implementation custom rules.
-pub trait Rule: Clone {
+pub trait Rule: Clone + Send + Sync {
     /// custom define returning message type
     type Message;
 
@@ -572,14 +919,29 @@ pub trait Rule: Clone {
     /// Default rule error message, when validate fails, return the message to user
     fn message(&self) -> Self::Message;
 
+    /// error message when the field is not found in the data, default is [`Rule::message`]
+    fn missing_message(&self) -> Self::Message {
+        self.message()
+    }
+
+    /// parameters of the rule, e.g. `[("min", 6), ("max", 12)]` of `Length(6..=12)`,
+    /// they are listed by [`Validator::fields`], default is empty.
+    ///
+    /// [`Validator::fields`]: crate::register::InnerValidator::fields
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     /// when the method return true, call_message will return Ok(()), or else return Err(String)
     ///
-    /// *Panic*
-    /// when not found value
+    /// it returns false when the field is not found
     #[must_use]
     fn call_with_relate(&mut self, data: &mut ValueMap) -> bool {
-        self.call(data.current_mut().expect("not found value with fields"))
+        match data.current_mut() {
+            Some(value) => self.call(value),
+            None => false,
+        }
     }
 
     /// Rule specific implementation, data is current field's value
@@ -589,16 +951,22 @@ pub trait Rule: Clone {
 
 impl<T> CoreRule<ValueMap, ()> for T
 where
-    T: Rule + 'static + Clone,
+    T: Rule + 'static,
 {
     type Message = T::Message;
 
     const THE_NAME: &'static str = T::NAME;
 
+    fn params(&self) -> Vec<(&'static str, Value)> {
+        Rule::params(self)
+    }
+
     /// Rule specific implementation, data is gived type all field's value, and current field index.
     fn call(&mut self, data: &mut ValueMap) -> Result<(), Self::Message> {
         if self.call_with_relate(data) {
             Ok(())
+        } else if data.current().is_none() {
+            Err(self.missing_message())
         } else {
             Err(self.message())
         }
@@ -607,15 +975,96 @@ where
 
 impl<F, V, M> CoreRule<ValueMap, V> for F
 where
-    F: for<'a> FnOnce(&'a mut V) -> Result<(), M> + 'static + Clone,
+    F: for<'a> FnOnce(&'a mut V) -> Result<(), M> + 'static + Clone + Send + Sync,
+    V: FromValue,
+{
+    type Message = M;
+
+    const THE_NAME: &'static str = "custom";
+
+    const IS_CUSTOM: bool = true;
+
+    /// the closure is skipped when the field is not found, wrap it with [`checked`] to report it
+    ///
+    /// # Panic
+    ///
+    /// the value is not the type of argument, wrap the closure with [`checked`] to report it
+    fn call(&mut self, data: &mut ValueMap) -> Result<(), Self::Message> {
+        match V::from_value(data) {
+            Some(val) => self.clone()(val),
+            None => {
+                assert!(data.current().is_none(), "argument type can not be matched");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<F, V, C, M> CoreRule<ValueMap, (V, C)> for F
+where
+    F: for<'a> FnOnce(&'a mut V, &'a C) -> Result<(), M> + 'static + Clone + Send + Sync,
     V: FromValue,
+    C: Any + Send + Sync,
+    M: ClosureMessage,
 {
     type Message = M;
 
     const THE_NAME: &'static str = "custom";
 
+    const IS_CUSTOM: bool = true;
+
+    /// it fails with [`ClosureMessage::missing`] when the field is not found,
+    /// or with [`ClosureMessage::mismatch`] when the value or context is not the type of argument
     fn call(&mut self, data: &mut ValueMap) -> Result<(), Self::Message> {
-        let val = V::from_value(data).expect("argument type can not be matched");
-        self.clone()(val)
+        let context = data.take_context();
+        let res = match context.as_deref().and_then(|ctx| ctx.downcast_ref::<C>()) {
+            Some(ctx) => match V::from_value(data) {
+                Some(val) => self.clone()(val, ctx),
+                None => Err(closure_error::<V, M>(data)),
+            },
+            None => Err(M::mismatch(std::any::type_name::<C>())),
+        };
+        data.restore_context(context);
+        res
+    }
+}
+
+/// the error of closure when its argument can not be got from the data
+fn closure_error<V, M: ClosureMessage>(data: &ValueMap) -> M {
+    if data.current().is_none() {
+        M::missing()
+    } else {
+        M::mismatch(std::any::type_name::<V>())
+    }
+}
+
+/// the messages of closures, which are not made by the closures themselves
+///
+/// e.g. the field of a [`checked`] closure is not found, or the context of [`custom_with_context`] is not passed.
+pub trait ClosureMessage {
+    /// the field is not found in the data
+    fn missing() -> Self;
+
+    /// the value or context is not the `expected` type
+    fn mismatch(expected: &'static str) -> Self;
+}
+
+impl ClosureMessage for String {
+    fn missing() -> Self {
+        "this field is missing".to_owned()
+    }
+
+    fn mismatch(expected: &'static str) -> Self {
+        format!("the type `{expected}` can not be matched")
+    }
+}
+
+impl ClosureMessage for &'static str {
+    fn missing() -> Self {
+        "this field is missing"
+    }
+
+    fn mismatch(_expected: &'static str) -> Self {
+        "the type can not be matched"
     }
 }
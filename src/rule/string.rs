@@ -2,7 +2,7 @@ use super::{CoreRule, RuleList};
 
 pub fn custom<F, M>(f: F) -> RuleList<String, M>
 where
-    F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+    F: FnOnce(&mut String) -> Result<(), M> + Clone + Send + Sync + 'static,
     M: 'static,
 {
     RuleList::from_fn(f)
@@ -23,7 +23,7 @@ pub trait StringRuleExt<M>: private::Sealed {
 
     fn custom<F>(self, other: F) -> RuleList<String, M>
     where
-        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static;
+        F: FnOnce(&mut String) -> Result<(), M> + Clone + Send + Sync + 'static;
 }
 
 impl<S, M> StringRuleExt<M> for S
@@ -40,13 +40,13 @@ where
 
     fn custom<F>(self, fun: F) -> RuleList<String, M>
     where
-        F: FnOnce(&mut String) -> Result<(), M> + Clone + 'static,
+        F: FnOnce(&mut String) -> Result<(), M> + Clone + Send + Sync + 'static,
     {
         RuleList::append_fn(self, fun)
     }
 }
 
-pub trait StringRule: Clone {
+pub trait StringRule: Clone + Send + Sync {
     /// custom define returning message type
     type Message;
 
@@ -65,7 +65,7 @@ pub trait StringRule: Clone {
 
 impl<T> CoreRule<String, ()> for T
 where
-    T: StringRule + 'static + Clone,
+    T: StringRule + 'static,
 {
     type Message = T::Message;
 
@@ -83,7 +83,7 @@ where
 
 impl<F, M> CoreRule<String, ((), ())> for F
 where
-    F: for<'a> FnOnce(&'a mut String) -> Result<(), M> + 'static + Clone,
+    F: for<'a> FnOnce(&'a mut String) -> Result<(), M> + 'static + Clone + Send + Sync,
 {
     type Message = M;
     const THE_NAME: &'static str = "custom";
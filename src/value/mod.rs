@@ -21,9 +21,9 @@
 //! # }
 //! ```
 
-use std::{collections::BTreeMap, fmt::Display, mem};
+use std::{any::Any, collections::BTreeMap, fmt::Display, mem};
 
-use crate::register::{FieldName, FieldNames, Parser};
+use crate::register::{FieldName, FieldNames, Parser, ParserError};
 
 use self::float::{Float32, Float64};
 
@@ -104,6 +104,8 @@ pub enum Value {
 pub struct ValueMap {
     pub(crate) value: Value,
     pub(crate) index: FieldNames,
+    excluded: bool,
+    context: Option<Box<dyn Any + Send + Sync>>,
 }
 
 pub trait FromValue {
@@ -115,27 +117,61 @@ impl ValueMap {
         Self {
             value,
             index: FieldNames::default(),
+            excluded: false,
+            context: None,
         }
     }
 
-    /// change index
-    pub fn index(&mut self, index: FieldNames) {
-        debug_assert!(
-            self.value.get_with_names(&index).is_some(),
-            "field `{}` is not exist",
-            index.as_str()
-        );
+    pub(crate) fn with_context<C>(value: Value, context: C) -> Self
+    where
+        C: Any + Send + Sync,
+    {
+        Self {
+            context: Some(Box::new(context)),
+            ..Self::new(value)
+        }
+    }
 
+    /// change index, the field may be not exist
+    pub fn index(&mut self, index: FieldNames) {
         self.index = index;
+        self.excluded = false;
     }
 
     /// Takes the FieldNames out of the ValueMap
     pub fn take_index(&mut self) -> FieldNames {
         let mut x = FieldNames::default();
         mem::swap(&mut self.index, &mut x);
+        self.excluded = false;
         x
     }
 
+    /// exclude current field from validation, the rest rules of the field will be skipped.
+    pub fn exclude(&mut self) {
+        self.excluded = true;
+    }
+
+    /// check current field is excluded or not
+    pub fn is_excluded(&self) -> bool {
+        self.excluded
+    }
+
+    /// get external context, it is passed by [`validate_with_context`],
+    /// returning `None` when it is not passed or the type is not matched.
+    ///
+    /// [`validate_with_context`]: crate::register::Validator::validate_with_context
+    pub fn context<C: Any>(&self) -> Option<&C> {
+        self.context.as_ref()?.downcast_ref()
+    }
+
+    pub(crate) fn take_context(&mut self) -> Option<Box<dyn Any + Send + Sync>> {
+        self.context.take()
+    }
+
+    pub(crate) fn restore_context(&mut self, context: Option<Box<dyn Any + Send + Sync>>) {
+        self.context = context;
+    }
+
     pub(crate) fn as_index(&self) -> &FieldNames {
         &self.index
     }
@@ -169,7 +205,9 @@ impl Value {
     /// get field value by field name
     pub fn get_with_name(&self, name: &FieldName) -> Option<&Value> {
         match (name, self) {
-            (FieldName::Array(i), Value::Array(vec)) => vec.get(*i),
+            (FieldName::Array(i), Value::Array(vec)) | (FieldName::Array(i), Value::Tuple(vec)) => {
+                vec.get(*i)
+            }
             (FieldName::Tuple(i), Value::Tuple(vec))
             | (FieldName::Tuple(i), Value::TupleStruct(vec))
             | (FieldName::Tuple(i), Value::NewtypeStruct(vec))
@@ -181,6 +219,15 @@ impl Value {
             (FieldName::StructVariant(str), Value::StructVariant(_, btree)) => {
                 btree.get(&Value::StructVariantKey(str.to_string()))
             }
+            (FieldName::MapKey(str), Value::Map(btree))
+            | (FieldName::Literal(str), Value::Map(btree)) => {
+                find_map_key(btree, str).and_then(|key| btree.get(&key))
+            }
+            // step into `Some`, e.g. `profile.avatar.url` with `avatar: Option<Avatar>`
+            (name, Value::Option(opt)) => match opt.as_ref() {
+                Some(value) => value.get_with_name(name),
+                None => None,
+            },
             _ => None,
         }
     }
@@ -206,7 +253,9 @@ impl Value {
     /// get field mutable value by field name
     pub fn get_with_name_mut(&mut self, name: &FieldName) -> Option<&mut Value> {
         match (name, self) {
-            (FieldName::Array(i), Value::Array(vec)) => vec.get_mut(*i),
+            (FieldName::Array(i), Value::Array(vec)) | (FieldName::Array(i), Value::Tuple(vec)) => {
+                vec.get_mut(*i)
+            }
             (FieldName::Tuple(i), Value::Tuple(vec))
             | (FieldName::Tuple(i), Value::TupleStruct(vec))
             | (FieldName::Tuple(i), Value::NewtypeStruct(vec))
@@ -218,6 +267,14 @@ impl Value {
             (FieldName::StructVariant(str), Value::StructVariant(_, btree)) => {
                 btree.get_mut(&Value::StructVariantKey(str.to_string()))
             }
+            (FieldName::MapKey(str), Value::Map(btree))
+            | (FieldName::Literal(str), Value::Map(btree)) => {
+                find_map_key(btree, str).and_then(|key| btree.get_mut(&key))
+            }
+            (name, Value::Option(opt)) => match opt.as_mut() {
+                Some(value) => value.get_with_name_mut(name),
+                None => None,
+            },
             _ => None,
         }
     }
@@ -240,6 +297,228 @@ impl Value {
         }
     }
 
+    /// step into `Some` until it is not `Option`, returning `None` when it is `None`
+    fn step_into_some(&self) -> Option<&Value> {
+        match self {
+            Value::Option(opt) => opt.as_ref().as_ref().and_then(Value::step_into_some),
+            value => Some(value),
+        }
+    }
+
+    /// check the value of names is `None`, or it is absent because of `None` parent,
+    /// e.g. `profile.avatar.url` with `avatar: None`
+    pub(crate) fn is_none_with_names(&self, names: &FieldNames) -> bool {
+        let mut value = self;
+        let mut parser = Parser::new(names.as_str());
+        loop {
+            if matches!(value, Value::Option(opt) if opt.is_none()) {
+                break true;
+            }
+            match parser.next_name() {
+                Ok(Some(name)) => match value.get_with_name(&name) {
+                    Some(v) => value = v,
+                    None => break false,
+                },
+                _ => break false,
+            }
+        }
+    }
+
+    /// expand `[*]` in field names to every item's index, e.g.
+    /// `items[*].name` to `items[0].name`, `items[1].name` ...
+    ///
+    /// on the map, it is expanded to every key, e.g. `metadata["region"]`, non-string keys
+    /// are written by their display form, e.g. `scores["7"]`.
+    /// when `[*]` is not matching an array, tuple or map, it will be expanded to nothing.
+    ///
+    /// it is error when the key of map can not be written in field names, e.g. it contains `"`,
+    /// or it is composite, e.g. a tuple.
+    pub(crate) fn expand_names(&self, names: &FieldNames) -> Result<Vec<FieldNames>, ParserError> {
+        let mut list: Vec<(Vec<FieldName>, Option<&Value>)> = vec![(Vec::new(), Some(self))];
+        let mut parser = Parser::new(names.as_str());
+        while let Some(name) = parser.next_name()? {
+            list = match name {
+                FieldName::Wildcard => {
+                    let mut expanded = Vec::new();
+                    for (path, value) in list {
+                        let items: Vec<(FieldName, &Value)> =
+                            match value.and_then(Value::step_into_some) {
+                                Some(Value::Array(vec)) => vec
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, item)| (FieldName::Array(i), item))
+                                    .collect(),
+                                Some(Value::Tuple(vec)) => vec
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, item)| {
+                                        u8::try_from(i)
+                                            .map(|i| (FieldName::Tuple(i), item))
+                                            .map_err(|_| ParserError::ParseTupleIndex)
+                                    })
+                                    .collect::<Result<_, _>>()?,
+                                Some(Value::Map(btree)) => btree
+                                    .iter()
+                                    .map(|(key, item)| {
+                                        map_key(key)
+                                            .map(|key| (FieldName::MapKey(key), item))
+                                            .ok_or(ParserError::CompositeKey)
+                                    })
+                                    .collect::<Result<_, _>>()?,
+                                _ => Vec::new(),
+                            };
+                        for (name, item) in items {
+                            // the key can not be written as `["key"]`, so it is not addressable
+                            if matches!(&name, FieldName::MapKey(key) if key.contains('"')) {
+                                return Err(ParserError::QuoteInKey);
+                            }
+                            let mut path = path.clone();
+                            path.push(name);
+                            expanded.push((path, Some(item)));
+                        }
+                    }
+                    expanded
+                }
+                name => list
+                    .into_iter()
+                    .map(|(mut path, value)| {
+                        let item = value.and_then(|v| v.get_with_name(&name));
+                        path.push(name.clone());
+                        (path, item)
+                    })
+                    .collect(),
+            };
+        }
+
+        Ok(list.into_iter().map(|(path, _)| path.into()).collect())
+    }
+
+    /// collect the keys of struct and map which are not covered by the known names,
+    /// e.g. `address.zip` is unknown when only `address.city` is known.
+    ///
+    /// `[*]` of the known names matches every item, and a known name covers all of its children.
+    pub(crate) fn unknown_names(&self, known: &[FieldNames]) -> Vec<FieldNames> {
+        let known: Vec<Vec<FieldName>> = known
+            .iter()
+            .map(|names| {
+                let mut parser = Parser::new(names.as_str());
+                let mut list = Vec::new();
+                while let Ok(Some(name)) = parser.next_name() {
+                    list.push(name);
+                }
+                list
+            })
+            .collect();
+        let known: Vec<&[FieldName]> = known.iter().map(Vec::as_slice).collect();
+
+        let mut unknown = Vec::new();
+        self.collect_unknown(&known, &mut Vec::new(), &mut unknown);
+        unknown
+    }
+
+    fn collect_unknown(
+        &self,
+        known: &[&[FieldName]],
+        path: &mut Vec<FieldName>,
+        unknown: &mut Vec<FieldNames>,
+    ) {
+        fn is_matched(known: &FieldName, child: &FieldName) -> bool {
+            match (known, child) {
+                (
+                    FieldName::Wildcard,
+                    FieldName::Array(_) | FieldName::Tuple(_) | FieldName::MapKey(_),
+                ) => true,
+                (FieldName::Array(a), FieldName::Tuple(b))
+                | (FieldName::Tuple(b), FieldName::Array(a)) => *a == *b as usize,
+                (FieldName::Literal(a), FieldName::MapKey(b)) => a == b,
+                (known, child) => known == child,
+            }
+        }
+
+        if known.iter().any(|names| names.is_empty()) {
+            return;
+        }
+
+        // only the keys of struct and map are reported, items of array and tuple are not
+        let (children, is_key): (Vec<(FieldName, &Value)>, bool) = match self {
+            Value::Option(opt) => {
+                if let Some(value) = opt.as_ref() {
+                    value.collect_unknown(known, path, unknown);
+                }
+                return;
+            }
+            Value::Struct(btree) => (
+                btree
+                    .iter()
+                    .filter_map(|(key, value)| match key {
+                        Value::StructKey(key) => Some((FieldName::Literal(key.clone()), value)),
+                        _ => None,
+                    })
+                    .collect(),
+                true,
+            ),
+            Value::StructVariant(_, btree) => (
+                btree
+                    .iter()
+                    .filter_map(|(key, value)| match key {
+                        Value::StructVariantKey(key) => {
+                            Some((FieldName::StructVariant(key.clone()), value))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+                true,
+            ),
+            Value::Map(btree) => (
+                btree
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        map_key(key).map(|key| (FieldName::MapKey(key), value))
+                    })
+                    .collect(),
+                true,
+            ),
+            Value::Array(vec) => (
+                vec.iter()
+                    .enumerate()
+                    .map(|(i, value)| (FieldName::Array(i), value))
+                    .collect(),
+                false,
+            ),
+            Value::Tuple(vec)
+            | Value::TupleStruct(vec)
+            | Value::NewtypeStruct(vec)
+            | Value::Enum(_, vec)
+            | Value::TupleVariant(_, vec) => (
+                // the index out of `u8` can not be named by any field, so it has no known children
+                vec.iter()
+                    .enumerate()
+                    .filter_map(|(i, value)| {
+                        u8::try_from(i).ok().map(|i| (FieldName::Tuple(i), value))
+                    })
+                    .collect(),
+                false,
+            ),
+            _ => return,
+        };
+
+        for (name, value) in children {
+            let rest: Vec<&[FieldName]> = known
+                .iter()
+                .filter(|names| is_matched(&names[0], &name))
+                .map(|names| &names[1..])
+                .collect();
+
+            path.push(name);
+            if !rest.is_empty() {
+                value.collect_unknown(&rest, path, unknown);
+            } else if is_key {
+                unknown.push(path.clone().into());
+            }
+            path.pop();
+        }
+    }
+
     pub fn is_leaf(&self) -> bool {
         matches!(
             self,
@@ -363,6 +642,38 @@ impl FromValue for Bytes {
     }
 }
 
+/// the name of map key in field path, non-string keys are named by their display form,
+/// e.g. `scores["7"]` of `HashMap<u32, _>`, composite keys have not name
+fn map_key(key: &Value) -> Option<String> {
+    match key {
+        Value::String(key) => Some(key.clone()),
+        Value::Uint8(_)
+        | Value::Int8(_)
+        | Value::Uint16(_)
+        | Value::Int16(_)
+        | Value::Uint32(_)
+        | Value::Int32(_)
+        | Value::Uint64(_)
+        | Value::Int64(_)
+        | Value::Float32(_)
+        | Value::Float64(_)
+        | Value::Boolean(_)
+        | Value::Char(_) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+/// find the key of map by its name in field path, see [`map_key`]
+fn find_map_key(map: &BTreeMap<Value, Value>, name: &str) -> Option<Value> {
+    let key = Value::String(name.to_owned());
+    if map.contains_key(&key) {
+        return Some(key);
+    }
+    map.keys()
+        .find(|key| !matches!(key, Value::String(_)) && map_key(key).as_deref() == Some(name))
+        .cloned()
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -384,3 +695,185 @@ impl Display for Value {
         }
     }
 }
+
+#[test]
+fn test_option_names() {
+    use crate::ser::to_value;
+
+    #[derive(serde::Serialize)]
+    struct Avatar {
+        url: &'static str,
+    }
+
+    let value = to_value((Some(Avatar { url: "foo" }), None::<Avatar>)).unwrap();
+    assert_eq!(
+        value.get_with_names(&"0.url".into()),
+        Some(&Value::String("foo".into()))
+    );
+    assert!(value.get_with_names(&"1.url".into()).is_none());
+    assert!(value.is_none_with_names(&"1.url".into()));
+    assert!(value.is_none_with_names(&"1".into()));
+    assert!(!value.is_none_with_names(&"0.url".into()));
+    assert!(!value.is_none_with_names(&"0.name".into()));
+
+    let value = to_value(Some(vec![1_u8, 2])).unwrap();
+    assert_eq!(value.expand_names(&"[*]".into()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_expand_names() {
+    use crate::ser::to_value;
+
+    #[derive(serde::Serialize)]
+    struct Item {
+        name: &'static str,
+        tags: Vec<u8>,
+    }
+
+    let value = to_value(vec![
+        Item {
+            name: "foo",
+            tags: vec![1, 2],
+        },
+        Item {
+            name: "bar",
+            tags: vec![],
+        },
+    ])
+    .unwrap();
+
+    let names = value.expand_names(&"[*].name".into()).unwrap();
+    assert_eq!(names, vec!["[0].name".into(), "[1].name".into()]);
+
+    let names = value.expand_names(&"[*].tags[*]".into()).unwrap();
+    assert_eq!(names, vec!["[0].tags[0]".into(), "[0].tags[1]".into()]);
+
+    let names = value.expand_names(&"[0].name[*]".into()).unwrap();
+    assert!(names.is_empty());
+
+    let names = value.expand_names(&"[1].name".into()).unwrap();
+    assert_eq!(names, vec![FieldNames::from("[1].name")]);
+
+    let value = to_value((1_u8, "foo")).unwrap();
+    let names = value.expand_names(&"[*]".into()).unwrap();
+    assert_eq!(names, vec!["0".into(), "1".into()]);
+    assert_eq!(
+        value.get_with_names(&names[1]),
+        Some(&Value::String("foo".into()))
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert("region", "eu");
+    map.insert("zone", "a");
+    let value = to_value(map).unwrap();
+    let names = value.expand_names(&"[*]".into()).unwrap();
+    assert_eq!(
+        names,
+        vec![
+            FieldNames::from(r#"["region"]"#),
+            FieldNames::from(r#"["zone"]"#)
+        ]
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert(r#"a"b"#, "eu");
+    let value = to_value(map).unwrap();
+    assert!(value.expand_names(&"[*]".into()).is_err());
+
+    // non-string keys are named by their display form
+    let value = to_value(BTreeMap::from([(7_u32, "a"), (10_u32, "b")])).unwrap();
+    let names = value.expand_names(&"[*]".into()).unwrap();
+    assert_eq!(
+        names,
+        vec![FieldNames::from(r#"["7"]"#), FieldNames::from(r#"["10"]"#)]
+    );
+    assert_eq!(
+        value.get_with_names(&names[1]),
+        Some(&Value::String("b".into()))
+    );
+
+    let value = to_value(BTreeMap::from([((1_u8, 2_u8), "a")])).unwrap();
+    assert!(value.expand_names(&"[*]".into()).is_err());
+}
+
+#[test]
+fn test_get_map() {
+    use crate::ser::to_value;
+    use std::collections::HashMap;
+
+    #[derive(serde::Serialize)]
+    struct Input {
+        metadata: HashMap<&'static str, &'static str>,
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("region", "eu");
+    metadata.insert("us-east.1", "a");
+
+    let mut value = to_value(Input { metadata }).unwrap();
+
+    assert_eq!(
+        value.get_with_names(&"metadata.region".into()),
+        Some(&Value::String("eu".into()))
+    );
+    assert_eq!(
+        value.get_with_names(&r#"metadata["region"]"#.into()),
+        Some(&Value::String("eu".into()))
+    );
+    assert_eq!(
+        value.get_with_names(&r#"metadata["us-east.1"]"#.into()),
+        Some(&Value::String("a".into()))
+    );
+    assert_eq!(value.get_with_names(&"metadata.zone".into()), None);
+
+    *value
+        .get_with_names_mut(&r#"metadata["region"]"#.into())
+        .unwrap() = Value::String("us".into());
+    assert_eq!(
+        value.get_with_names(&"metadata.region".into()),
+        Some(&Value::String("us".into()))
+    );
+}
+
+#[test]
+fn test_unknown_names() {
+    use std::collections::HashMap;
+
+    use crate::ser::to_value;
+
+    #[derive(serde::Serialize)]
+    struct Item {
+        name: &'static str,
+        title: &'static str,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Order {
+        id: u32,
+        items: Vec<Item>,
+        address: Option<HashMap<&'static str, &'static str>>,
+    }
+
+    let value = to_value(Order {
+        id: 1,
+        items: vec![Item {
+            name: "foo",
+            title: "bar",
+        }],
+        address: Some(HashMap::from([("city", "Paris"), ("zip", "75001")])),
+    })
+    .unwrap();
+
+    let names = value.unknown_names(&["items[*].name".into(), "address.city".into()]);
+    assert_eq!(
+        names,
+        vec![
+            "address[\"zip\"]".into(),
+            "id".into(),
+            "items[0].title".into()
+        ]
+    );
+
+    let names = value.unknown_names(&["id".into(), "items".into(), "address".into()]);
+    assert!(names.is_empty());
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "derive")]
+
+use serde::{Deserialize, Serialize};
+use valitron::{
+    available::{Message, MessageKind, Not, StartWith},
+    Validatable, Validate, Value,
+};
+
+#[derive(Serialize, Deserialize, Validate)]
+#[valitron(lenient)]
+struct Register {
+    #[valitron(
+        bail,
+        required,
+        length(3..=12),
+        rule = Not(StartWith("admin")),
+        message(required = "name is required")
+    )]
+    name: String,
+
+    #[valitron(trim, custom = strong, message(custom = "password is too weak"))]
+    #[serde(rename = "pwd")]
+    password: String,
+
+    #[valitron(confirm(password))]
+    r#confirm: String,
+
+    #[valitron(required_if(kind, "company"), nullable)]
+    company: Option<String>,
+
+    kind: String,
+
+    #[valitron(range(18..=60))]
+    age: u8,
+
+    items: Vec<Item>,
+
+    #[valitron(lt(items[0].price))]
+    discount: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Item {
+    price: u32,
+}
+
+#[allow(clippy::ptr_arg)]
+fn strong(password: &mut String) -> Result<(), Message> {
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err("weak".into())
+    }
+}
+
+fn register() -> Register {
+    Register {
+        name: "bob".into(),
+        password: " abc123 ".into(),
+        confirm: "abc123".into(),
+        company: Some("acme".into()),
+        kind: "company".into(),
+        age: 20,
+        items: vec![Item { price: 10 }],
+        discount: 5,
+    }
+}
+
+#[test]
+fn test_valid() {
+    let data = register().validate_mut(()).unwrap();
+    assert_eq!(data.password, "abc123");
+}
+
+#[test]
+fn test_invalid() {
+    let data = Register {
+        name: "".into(),
+        password: "abc".into(),
+        confirm: "abd".into(),
+        company: None,
+        age: 70,
+        discount: 10,
+        ..register()
+    };
+
+    let err = data.validate(()).unwrap_err();
+
+    assert_eq!(err["name"], vec![Message::from("name is required")]);
+    assert_eq!(err["pwd"], vec![Message::from("password is too weak")]);
+    assert_eq!(
+        err["confirm"],
+        vec![Message::new(MessageKind::Confirm("pwd".into()))]
+    );
+    assert_eq!(err["age"], vec![Message::new(MessageKind::Range)]);
+    assert_eq!(err["discount"].len(), 1);
+    assert_eq!(err.len(), 5);
+}
+
+#[test]
+fn test_validator() {
+    let validator = Register::validator();
+    assert!(std::ptr::eq(validator, Register::validator()));
+    assert!(validator.is_lenient());
+    assert!(validator.fields()[0].is_bail());
+
+    let fields = validator.fields();
+    let names: Vec<_> = fields.iter().map(|field| field.names().as_str()).collect();
+    assert_eq!(
+        names,
+        ["name", "pwd", "confirm", "company", "age", "discount"]
+    );
+    assert!(fields[3].is_nullable());
+    assert_eq!(
+        fields[5].rules()[0].param("other"),
+        Some(&Value::String("items[0].price".into()))
+    );
+}
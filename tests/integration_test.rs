@@ -54,7 +54,7 @@ fn age_limit(n: &mut u8) -> Result<(), Message> {
 fn weight_limit(v: &mut Value) -> Result<(), Message> {
     if let Value::Float32(n) = v {
         let n = n.get();
-        if n >= 40.0 && n <= 80.0 {
+        if (40.0..=80.0).contains(&n) {
             return Ok(());
         }
     }
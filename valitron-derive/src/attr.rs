@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parenthesized, punctuated::Punctuated, Attribute, Expr,
+    ExprLit, GenericArgument, Ident, Lit, LitStr, Member, Path, PathArguments, Token, Type,
+};
+
+/// attributes of struct, e.g. `#[valitron(bail)]`
+#[derive(Default)]
+pub(crate) struct Container {
+    pub(crate) bail: bool,
+    pub(crate) lenient: bool,
+}
+
+impl Container {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("valitron")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bail") {
+                    container.bail = true;
+                } else if meta.path.is_ident("lenient") {
+                    container.lenient = true;
+                } else {
+                    return Err(meta.error("unknown attribute, expected `bail` or `lenient`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(container)
+    }
+}
+
+/// a method call on `RuleList`, e.g. `.and(Required)`
+pub(crate) struct Rule {
+    /// it is `None` when the rule is an expression, e.g. `rule = Not(Required)`
+    pub(crate) name: Option<&'static str>,
+    pub(crate) call: TokenStream,
+}
+
+/// attributes of field, e.g. `#[valitron(required, length(6..=12))]`
+#[derive(Default)]
+pub(crate) struct Field {
+    pub(crate) rules: Vec<Rule>,
+    pub(crate) messages: Vec<(Ident, LitStr)>,
+
+    /// other fields referred by rules, e.g. `confirm(password)`, they are checked by compiler
+    pub(crate) references: Vec<Expr>,
+    pub(crate) bail: bool,
+    pub(crate) nullable: bool,
+}
+
+/// rules without arguments
+const UNIT_RULES: [(&str, &str); 4] = [
+    ("required", "Required"),
+    ("email", "Email"),
+    ("trim", "Trim"),
+    ("prohibited", "Prohibited"),
+];
+
+/// rules with a value
+const VALUE_RULES: [(&str, &str); 5] = [
+    ("length", "Length"),
+    ("start_with", "StartWith"),
+    ("end_with", "EndsWith"),
+    ("contains", "Contains"),
+    ("regex", "Regex::new"),
+];
+
+/// rules with another field
+const FIELD_RULES: [(&str, &str); 7] = [
+    ("confirm", "Confirm"),
+    ("lt", "Lt"),
+    ("elt", "Elt"),
+    ("gt", "Gt"),
+    ("egt", "Egt"),
+    ("required_with", "RequiredWith"),
+    ("required_without", "RequiredWithout"),
+];
+
+/// rules with another field and a value
+const CONDITION_RULES: [(&str, &str); 3] = [
+    ("required_if", "RequiredIf"),
+    ("required_unless", "RequiredUnless"),
+    ("exclude_if", "ExcludeIf"),
+];
+
+impl Field {
+    /// `renames` maps field idents to the names in serialized data
+    pub(crate) fn from_attrs(
+        attrs: &[Attribute],
+        ty: &Type,
+        renames: &HashMap<String, String>,
+    ) -> syn::Result<Self> {
+        let mut field = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("valitron")) {
+            attr.parse_nested_meta(|meta| field.parse_meta(meta, ty, renames))?;
+        }
+        Ok(field)
+    }
+
+    fn parse_meta(
+        &mut self,
+        meta: ParseNestedMeta<'_>,
+        ty: &Type,
+        renames: &HashMap<String, String>,
+    ) -> syn::Result<()> {
+        let ident = match meta.path.get_ident() {
+            Some(ident) => ident.unraw().to_string(),
+            None => return Err(meta.error("expected a rule name")),
+        };
+        let find = |list: &[(&'static str, &'static str)]| {
+            list.iter()
+                .find(|(name, _)| *name == ident)
+                .map(|(name, ty)| (*name, rule_path(ty)))
+        };
+
+        if let Some((name, ty)) = find(&UNIT_RULES) {
+            self.push(name, quote!(.and(#ty)));
+        } else if let Some((name, ty)) = find(&VALUE_RULES) {
+            let [value] = args(&meta, name, "a value")?;
+            self.push(name, quote!(.and(#ty(#value))));
+        } else if let Some((name, ty)) = find(&FIELD_RULES) {
+            let [other] = args(&meta, name, "a field")?;
+            let other = self.reference(other, renames)?;
+            self.push(name, quote!(.and(#ty(#other))));
+        } else if let Some((name, ty)) = find(&CONDITION_RULES) {
+            let [other, value] = args(&meta, name, "a field and a value")?;
+            let other = self.reference(other, renames)?;
+            self.push(name, quote!(.and(#ty(#other, #value))));
+        } else {
+            match ident.as_str() {
+                // the number type of range is the type of field, so `range(18..=60)` is enough
+                "range" => {
+                    // `Range` does not match `Value::Option`, so it would always fail
+                    if option_inner(ty).is_some() {
+                        return Err(meta.error("`range` does not support `Option<T>` fields"));
+                    }
+                    let [value] = args(&meta, "range", "a range")?;
+                    self.push(
+                        "range",
+                        quote!(.and(::valitron::available::Range::<_, #ty>::new(#value))),
+                    );
+                }
+                "custom" => {
+                    let path: Path = meta.value()?.parse()?;
+                    self.push("custom", quote!(.custom(#path)));
+                }
+                "rule" => {
+                    let rule: Expr = meta.value()?.parse()?;
+                    self.rules.push(Rule {
+                        name: None,
+                        call: quote!(.and(#rule)),
+                    });
+                }
+                "message" => meta.parse_nested_meta(|inner| {
+                    let rule = inner.path.require_ident()?.clone();
+                    let message: LitStr = inner.value()?.parse()?;
+                    self.messages.push((rule, message));
+                    Ok(())
+                })?,
+                "bail" => self.bail = true,
+                "nullable" => self.nullable = true,
+                _ => return Err(meta.error(format!("unknown rule `{ident}`"))),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, name: &'static str, call: TokenStream) {
+        self.rules.push(Rule {
+            name: Some(name),
+            call,
+        });
+    }
+
+    /// the names of rules, it is `None` when a name is unknown
+    pub(crate) fn names(&self) -> Option<Vec<&'static str>> {
+        self.rules.iter().map(|rule| rule.name).collect()
+    }
+
+    /// keep the expression to be checked, and turn it into the path of validator, e.g. `items[0].price`
+    fn reference(&mut self, expr: Expr, renames: &HashMap<String, String>) -> syn::Result<String> {
+        let path = field_path(&expr, renames)?;
+        self.references.push(expr);
+        Ok(path)
+    }
+}
+
+fn rule_path(ty: &str) -> TokenStream {
+    let path: Path = syn::parse_str(&format!("::valitron::available::{ty}"))
+        .expect("the rule path should be valid");
+    quote!(#path)
+}
+
+/// `T` of `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// the arguments in parentheses, e.g. `length(6..=12)`
+fn args<const N: usize>(
+    meta: &ParseNestedMeta<'_>,
+    name: &str,
+    expected: &str,
+) -> syn::Result<[Expr; N]> {
+    let error = || meta.error(format!("`{name}` expects {expected}"));
+    if !meta.input.peek(syn::token::Paren) {
+        return Err(error());
+    }
+
+    let content;
+    parenthesized!(content in meta.input);
+    let list: Punctuated<Expr, Token![,]> = Punctuated::parse_terminated(&content)?;
+
+    Vec::from_iter(list).try_into().map_err(|_| error())
+}
+
+fn field_path(expr: &Expr, renames: &HashMap<String, String>) -> syn::Result<String> {
+    match expr {
+        Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+            let ident = path.path.get_ident().unwrap().unraw().to_string();
+            Ok(renames.get(&ident).cloned().unwrap_or(ident))
+        }
+        Expr::Field(field) => {
+            let base = field_path(&field.base, renames)?;
+            match &field.member {
+                Member::Named(ident) => Ok(format!("{base}.{}", ident.unraw())),
+                Member::Unnamed(index) => Ok(format!("{base}.{}", index.index)),
+            }
+        }
+        Expr::Index(index) => match &*index.index {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => Ok(format!(
+                "{}[{}]",
+                field_path(&index.expr, renames)?,
+                int.base10_parse::<usize>()?
+            )),
+            other => Err(syn::Error::new_spanned(
+                other,
+                "expected an integer index, e.g. `items[0]`",
+            )),
+        },
+        other => Err(syn::Error::new_spanned(
+            other,
+            "expected a field, e.g. `password` or `address.zip`",
+        )),
+    }
+}
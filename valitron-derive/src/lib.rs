@@ -0,0 +1,305 @@
+//! Derive macro of [valitron], it is re-exported by `valitron` with `derive` feature.
+//!
+//! [valitron]: https://docs.rs/valitron
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parenthesized, parse_macro_input, Attribute, Data,
+    DataStruct, DeriveInput, Expr, Fields, GenericParam, LitStr, Token,
+};
+
+mod attr;
+
+/// # Derive validator of struct
+///
+/// the rules of fields are declared by `#[valitron(..)]` attributes,
+/// it implements [`Validate`], so the struct is validated by [`Validatable`]:
+///
+/// ```rust
+/// # use serde::Serialize;
+/// # use valitron::{available::Message, Validatable, Validate};
+/// #[derive(Serialize, Validate)]
+/// struct Register {
+///     #[valitron(required, length(3..=12), message(length = "name should be 3 to 12 characters"))]
+///     name: String,
+///
+///     #[valitron(required, custom = strong, bail)]
+///     password: String,
+///
+///     #[valitron(confirm(password))]
+///     password2: String,
+/// }
+///
+/// fn strong(password: &mut String) -> Result<(), Message> {
+///     if password.chars().any(|c| c.is_ascii_digit()) {
+///         Ok(())
+///     } else {
+///         Err("password should contain a digit".into())
+///     }
+/// }
+///
+/// let data = Register {
+///     name: "ab".into(),
+///     password: "abcdef".into(),
+///     password2: "abcdeg".into(),
+/// };
+///
+/// let err = data.validate(()).unwrap_err();
+/// assert_eq!(err.get("name").unwrap()[0].to_string(), "name should be 3 to 12 characters");
+/// assert_eq!(err.get("password").unwrap()[0].to_string(), "password should contain a digit");
+/// assert_eq!(err.len(), 3);
+/// ```
+///
+/// ## Field attributes
+///
+/// | attribute | rule |
+/// | --- | --- |
+/// | `required`, `email`, `trim`, `prohibited` | `Required`, `Email`, `Trim`, `Prohibited` |
+/// | `length(6..=12)`, `range(1..=10)` | `Length`, `Range` |
+/// | `start_with("a")`, `end_with("a")`, `contains("a")` | `StartWith`, `EndsWith`, `Contains` |
+/// | `regex("^[a-z]+$")` | `Regex` |
+/// | `confirm(password)`, `lt(max)`, `elt(max)`, `gt(min)`, `egt(min)` | `Confirm`, `Lt`, `Elt`, `Gt`, `Egt` |
+/// | `required_with(a)`, `required_without(a)` | `RequiredWith`, `RequiredWithout` |
+/// | `required_if(a, "b")`, `required_unless(a, "b")`, `exclude_if(a, "b")` | `RequiredIf`, `RequiredUnless`, `ExcludeIf` |
+/// | `custom = path::to::fn` | closure rule, see `custom` |
+/// | `rule = Not(Required)` | any rule expression |
+/// | `message(required = "..")` | message of the rule |
+/// | `bail`, `nullable` | see `RuleList::bail` and `RuleList::nullable` |
+///
+/// `#[valitron(bail)]` and `#[valitron(lenient)]` on struct are the settings of validator.
+///
+/// ## Checked at compile time
+///
+/// the fields referred by rules are checked against the struct, nested fields and indexes
+/// are also supported, e.g. `gt(items[0].price)`:
+///
+/// ```compile_fail
+/// # use serde::Serialize;
+/// # use valitron::Validate;
+/// #[derive(Serialize, Validate)]
+/// struct Register {
+///     password: String,
+///     #[valitron(confirm(pasword))]
+///     password2: String,
+/// }
+/// ```
+///
+/// and the rules of messages are checked against the rules of field,
+/// unless the field has `rule = ..`:
+///
+/// ```compile_fail
+/// # use serde::Serialize;
+/// # use valitron::Validate;
+/// #[derive(Serialize, Validate)]
+/// struct Register {
+///     #[valitron(length(3..=12), message(lenght = "name is too short"))]
+///     name: String,
+/// }
+/// ```
+///
+/// the number type of `range(..)` is the type of field, `Option<T>` fields are not supported:
+///
+/// ```compile_fail
+/// # use serde::Serialize;
+/// # use valitron::Validate;
+/// #[derive(Serialize, Validate)]
+/// struct Register {
+///     #[valitron(range(18..=60))]
+///     age: Option<u8>,
+/// }
+/// ```
+///
+/// the validator is built once, so generic structs are not supported, lifetimes are allowed.
+///
+/// the field names follow `#[serde(rename = "..")]` of fields,
+/// `#[serde(rename_all = "..")]` is not supported.
+///
+/// [`Validate`]: https://docs.rs/valitron/latest/valitron/register/trait.Validate.html
+/// [`Validatable`]: https://docs.rs/valitron/latest/valitron/register/trait.Validatable.html
+#[proc_macro_derive(Validate, attributes(valitron))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Validate` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    if let Some(attr) = find_serde(&input.attrs, "rename_all")? {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`rename_all` is not supported by `Validate`, rename the fields one by one",
+        ));
+    }
+
+    let mut renames = HashMap::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("the field should be named");
+        if let Some(rename) = serde_rename(&field.attrs)? {
+            renames.insert(ident.unraw().to_string(), rename.value());
+        }
+    }
+
+    // the static validator is shared by all instances of generic struct
+    if let Some(param) = input
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            "`Validate` can not be derived for generic structs, implement it by hand",
+        ));
+    }
+
+    let container = attr::Container::from_attrs(&input.attrs)?;
+
+    let mut rules = Vec::new();
+    let mut messages = Vec::new();
+    let mut references = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("the field should be named");
+        let name = ident.unraw().to_string();
+        let name = renames.get(&name).cloned().unwrap_or(name);
+        let mut attrs = attr::Field::from_attrs(&field.attrs, &field.ty, &renames)?;
+
+        if attrs.rules.is_empty() {
+            if !attrs.messages.is_empty() || attrs.bail || attrs.nullable {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!("the field `{name}` has not any rule"),
+                ));
+            }
+            continue;
+        }
+
+        if let Some(names) = attrs.names() {
+            if let Some((rule, _)) = attrs
+                .messages
+                .iter()
+                .find(|(rule, _)| !names.contains(&rule.unraw().to_string().as_str()))
+            {
+                return Err(syn::Error::new_spanned(
+                    rule,
+                    format!("the rule `{rule}` is not found in the rules of field `{name}`"),
+                ));
+            }
+        }
+
+        let calls = attrs.rules.iter().map(|rule| &rule.call);
+        let bail = attrs.bail.then(|| quote!(.bail()));
+        let nullable = attrs.nullable.then(|| quote!(.nullable()));
+        rules.push(quote! {
+            .rule(
+                #name,
+                ::valitron::rule::RuleList::<::valitron::ValueMap, ::valitron::available::Message>::default()
+                    #(#calls)* #bail #nullable
+            )
+        });
+
+        for (rule, message) in &attrs.messages {
+            let key = format!("{name}.{}", rule.unraw());
+            messages.push(quote!((#key, #message)));
+        }
+        references.append(&mut attrs.references);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let bail = container.bail.then(|| quote!(.bail()));
+    let lenient = container.lenient.then(|| quote!(.lenient()));
+    let messages = (!messages.is_empty()).then(|| quote!(.message([#(#messages),*])));
+
+    Ok(quote! {
+        impl #impl_generics ::valitron::register::Validate for #ident #ty_generics #where_clause {
+            type Message = ::valitron::available::Message;
+
+            fn validator() -> &'static ::valitron::Validator<'static, Self::Message> {
+                // the referred fields are checked by compiler, it is never called
+                let _ = |this: &Self| {
+                    #(let _ = &this.#references;)*
+                };
+
+                static VALIDATOR: ::std::sync::OnceLock<
+                    ::valitron::Validator<'static, ::valitron::available::Message>,
+                > = ::std::sync::OnceLock::new();
+                VALIDATOR.get_or_init(|| {
+                    ::valitron::Validator::new()
+                        #bail
+                        #lenient
+                        #(#rules)*
+                        #messages
+                })
+            }
+        }
+    })
+}
+
+/// find the attribute in `#[serde(..)]`, e.g. `rename_all`
+fn find_serde<'a>(attrs: &'a [Attribute], name: &str) -> syn::Result<Option<&'a Attribute>> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                found = Some(attr);
+            }
+            skip(&meta)
+        })?;
+    }
+    Ok(found)
+}
+
+/// `#[serde(rename = "..")]` or `#[serde(rename(serialize = ".."))]`
+fn serde_rename(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("rename") {
+                return skip(&meta);
+            }
+            if meta.input.peek(Token![=]) {
+                rename = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            meta.parse_nested_meta(|inner| {
+                if inner.path.is_ident("serialize") {
+                    rename = Some(inner.value()?.parse()?);
+                    Ok(())
+                } else {
+                    skip(&inner)
+                }
+            })
+        })?;
+    }
+    Ok(rename)
+}
+
+/// skip the value of other serde attributes, e.g. `default = "path"` or `bound(..)`
+fn skip(meta: &ParseNestedMeta<'_>) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        parenthesized!(content in meta.input);
+        content.parse::<TokenStream2>()?;
+    }
+    Ok(())
+}